@@ -0,0 +1,164 @@
+use uom::si::f64::{Angle, Length};
+
+use crate::{constants::EARTH_RADIUS, segment::ArcSegment, utility::diff_angle, Coordinates, Direction};
+
+impl ArcSegment {
+	/// Returns the DME arc (ARINC 424 AF leg) at `distance` around `navaid`, starting on `boundary_radial` and ending
+	/// at `fix`, flown in `direction` (`Left` or `Right`). Radials are true bearings from the navaid, so magnetic
+	/// radials must have the station declination applied first.
+	///
+	/// Returns `None` if `fix` is not within `tolerance` of the arc
+	pub fn dme_arc(
+		navaid: Coordinates, distance: Length, boundary_radial: Angle, fix: Coordinates, direction: Direction,
+		tolerance: Length,
+	) -> Option<ArcSegment> {
+		if (navaid.distance_to(fix) - distance).abs() > tolerance {
+			return None;
+		}
+
+		Some(ArcSegment {
+			centre: navaid,
+			radius: distance,
+			start: navaid.bearing_distance(boundary_radial, distance),
+			end: fix,
+			direction,
+		})
+	}
+
+	/// Returns the constant radius arc (ARINC 424 RF leg) around `centre` from `start` to `end`, flown in `direction`
+	/// (`Left` or `Right`). The radius is the distance from `centre` to `start`.
+	///
+	/// Returns `None` if `end` is not within `tolerance` of the arc
+	pub fn radius_to_fix(
+		centre: Coordinates, start: Coordinates, end: Coordinates, direction: Direction, tolerance: Length,
+	) -> Option<ArcSegment> {
+		let radius = centre.distance_to(start);
+		if (centre.distance_to(end) - radius).abs() > tolerance {
+			return None;
+		}
+
+		Some(ArcSegment {
+			centre,
+			radius,
+			start,
+			end,
+			direction,
+		})
+	}
+
+	/// Returns the distance along the arc from its start to the point abeam `point`. Points abeam the part of the
+	/// circle not covered by the arc are measured from whichever end of the arc they are closer to, so points before
+	/// the start give a negative distance, and points after the end give a distance greater than the arc length
+	pub fn along_track_distance(&self, point: Coordinates) -> Length {
+		let sweep = self.sweep().abs();
+		let mut angle = diff_angle(
+			self.centre.bearing_to(self.start),
+			self.centre.bearing_to(point),
+			self.turn_direction(),
+		)
+		.abs();
+
+		if angle > sweep + (Angle::FULL_TURN - sweep) / 2.0 {
+			angle -= Angle::FULL_TURN;
+		}
+
+		let radial_distance: Angle = (self.radius / EARTH_RADIUS).into();
+		EARTH_RADIUS * radial_distance.sin() * angle.value
+	}
+
+	/// Returns the distance of `point` from the arc. The distance is positive when `point` is to the right of the arc,
+	/// and negative when it is to the left
+	pub fn cross_track_distance(&self, point: Coordinates) -> Length {
+		let outside = self.centre.distance_to(point) - self.radius;
+
+		match self.turn_direction() {
+			Direction::Left => outside,
+			_ => -outside,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use uom::{
+		si::{angle::degree, length::nautical_mile},
+		ConstZero,
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_dme_arc() {
+		let navaid = Coordinates::new(40.0, -75.0);
+		let distance = Length::new::<nautical_mile>(12.0);
+		let tolerance = Length::new::<nautical_mile>(0.1);
+		let fix = navaid.bearing_distance(Angle::new::<degree>(120.0), distance);
+
+		let arc = ArcSegment::dme_arc(
+			navaid,
+			distance,
+			Angle::new::<degree>(30.0),
+			fix,
+			Direction::Right,
+			tolerance,
+		)
+		.unwrap();
+		assert_uom_eq!(arc.sweep(), Angle::new::<degree>(90.0), 1e-6);
+		assert_uom_eq!(arc.length(), distance * PI / 2.0, 1.0);
+		assert_eq!(arc.end, fix);
+
+		let abeam = navaid.bearing_distance(Angle::new::<degree>(60.0), Length::new::<nautical_mile>(13.0));
+		assert_uom_eq!(arc.along_track_distance(abeam), distance * PI / 6.0, 1.0);
+		assert_uom_eq!(
+			arc.cross_track_distance(abeam),
+			Length::new::<nautical_mile>(-1.0),
+			1e-3
+		);
+
+		let before = navaid.bearing_distance(Angle::new::<degree>(10.0), distance);
+		assert_uom_eq!(arc.along_track_distance(before), -distance * PI / 9.0, 1.0);
+
+		let off_arc = navaid.bearing_distance(Angle::new::<degree>(120.0), Length::new::<nautical_mile>(12.5));
+		assert!(ArcSegment::dme_arc(
+			navaid,
+			distance,
+			Angle::new::<degree>(30.0),
+			off_arc,
+			Direction::Right,
+			tolerance,
+		)
+		.is_none());
+	}
+
+	#[test]
+	fn test_radius_to_fix() {
+		let centre = Coordinates::new(0.0, 179.99);
+		let radius = Length::new::<nautical_mile>(3.0);
+		let start = centre.bearing_distance(Angle::new::<degree>(90.0), radius);
+		let end = centre.bearing_distance(Angle::new::<degree>(340.0), radius);
+		let tolerance = Length::new::<nautical_mile>(0.01);
+
+		let arc = ArcSegment::radius_to_fix(centre, start, end, Direction::Left, tolerance).unwrap();
+		assert_uom_eq!(arc.sweep(), Angle::new::<degree>(-110.0), 1e-6);
+
+		let inside = centre.bearing_distance(Angle::new::<degree>(0.0), Length::new::<nautical_mile>(2.5));
+		assert_uom_eq!(
+			arc.cross_track_distance(inside),
+			Length::new::<nautical_mile>(-0.5),
+			1e-3
+		);
+		assert_uom_eq!(arc.along_track_distance(inside), radius * PI / 2.0, 1.0);
+
+		assert!(ArcSegment::radius_to_fix(
+			centre,
+			start,
+			centre.bearing_distance(Angle::ZERO, Length::new::<nautical_mile>(3.1)),
+			Direction::Left,
+			tolerance,
+		)
+		.is_none());
+	}
+}
@@ -8,7 +8,7 @@ impl Coordinates {
 		let x = self.lat.cos() * to.lat.sin() - self.lat.sin() * to.lat.cos() * (to.long - self.long).cos();
 
 		let theta = y.atan2(x);
-		return (theta + Angle::FULL_TURN) % Angle::FULL_TURN;
+		(theta + Angle::FULL_TURN) % Angle::FULL_TURN
 	}
 }
 
@@ -0,0 +1,213 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{polygon::Polygon, Coordinates, Direction};
+
+/// Largest distance the chords approximating the round ends and joins of a buffer may stray from the true arc
+const MAX_CHORD_ERROR: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 50.0,
+};
+
+/// Longest distance between the points used to approximate the sides of a buffer
+const SIDE_STEP: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 92600.0,
+};
+
+impl Polygon {
+	/// Returns the polygons covering every point within `half_width` of the great circle polyline `route`, with round
+	/// ends and joins.
+	///
+	/// Each leg is swept into its own region with round ends, and the regions are unioned. The round end of one leg
+	/// forms the join with the next on the outside of a turn, and the union trims the overlapping sides on the inside,
+	/// so tight turns and legs which cross further along the route need no special handling
+	pub fn corridor(route: &[Coordinates], half_width: Length) -> Vec<Polygon> {
+		if route.len() == 1 {
+			return vec![stadium(route[0], route[0], half_width)];
+		}
+
+		merge(
+			route
+				.windows(2)
+				.map(|leg| stadium(leg[0], leg[1], half_width))
+				.collect(),
+		)
+	}
+
+	/// Returns the polygons covering every point within `distance` of `self` when `distance` is positive, or every
+	/// point of `self` at least `-distance` from its boundary when `distance` is negative. Corners are rounded
+	pub fn buffer(&self, distance: Length) -> Vec<Polygon> {
+		if distance == Length::ZERO {
+			return vec![self.clone()];
+		}
+
+		let edges: Vec<Polygon> = std::iter::once(self.exterior())
+			.chain(self.interiors().iter().map(|ring| ring.as_slice()))
+			.flat_map(|ring| (0..ring.len()).map(move |i| stadium(ring[i], ring[(i + 1) % ring.len()], distance.abs())))
+			.collect();
+
+		if distance > Length::ZERO {
+			merge(std::iter::once(self.clone()).chain(edges).collect())
+		} else {
+			merge(edges).iter().fold(vec![self.clone()], |remaining, boundary| {
+				remaining
+					.iter()
+					.flat_map(|polygon| polygon.difference(boundary))
+					.collect()
+			})
+		}
+	}
+}
+
+/// Returns the region within `half_width` of the great circle segment from `start` to `end`, bounded by its offset
+/// sides and a half circle around each end
+fn stadium(start: Coordinates, end: Coordinates, half_width: Length) -> Polygon {
+	let length = start.distance_to(end);
+	let mut points = Vec::new();
+
+	if length < half_width / 1e6 {
+		return Polygon::new(start.small_circle_polyline(half_width, MAX_CHORD_ERROR), vec![]);
+	}
+
+	let initial_course = start.bearing_to(end);
+	let final_course = end.bearing_to(start) + Angle::HALF_TURN;
+	let steps = (length / SIDE_STEP).value.ceil().max(1.0) as usize;
+
+	let side = |fraction: f64, offset: Angle| {
+		let point = start.bearing_distance(initial_course, length * fraction);
+		let course = if fraction < 1.0 {
+			point.bearing_to(end)
+		} else {
+			final_course
+		};
+		point.bearing_distance(course + offset, half_width)
+	};
+
+	for i in 0..steps {
+		points.push(side(i as f64 / steps as f64, -Angle::HALF_TURN / 2.0));
+	}
+	points.extend(end.arc_polyline(
+		half_width,
+		final_course - Angle::HALF_TURN / 2.0,
+		final_course + Angle::HALF_TURN / 2.0,
+		Direction::Right,
+		MAX_CHORD_ERROR,
+	));
+	for i in (1..steps).rev() {
+		points.push(side(i as f64 / steps as f64, Angle::HALF_TURN / 2.0));
+	}
+	points.extend(start.arc_polyline(
+		half_width,
+		initial_course + Angle::HALF_TURN / 2.0,
+		initial_course - Angle::HALF_TURN / 2.0,
+		Direction::Right,
+		MAX_CHORD_ERROR,
+	));
+
+	Polygon::new(points, vec![])
+}
+
+/// Unions overlapping polygons together, leaving separate polygons for disjoint regions
+fn merge(polygons: Vec<Polygon>) -> Vec<Polygon> {
+	let mut merged: Vec<Polygon> = Vec::new();
+	for polygon in polygons {
+		let mut current = polygon;
+		let mut remaining = Vec::with_capacity(merged.len());
+		for other in merged {
+			let mut union = current.union(&other);
+			if union.len() == 1 {
+				current = union.remove(0);
+			} else {
+				remaining.push(other);
+			}
+		}
+		remaining.push(current);
+		merged = remaining;
+	}
+
+	merged
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use uom::si::{f64::Area, length::nautical_mile};
+
+	use super::*;
+	use crate::assert_about_eq;
+
+	fn relative_error(a: Area, b: Area) -> f64 { ((a - b) / b).value.abs() }
+
+	#[test]
+	fn test_corridor_straight() {
+		let half_width = Length::new::<nautical_mile>(5.0);
+		let start = Coordinates::new(0.0, 0.0);
+		let end = Coordinates::new(0.0, 2.0);
+
+		let corridor = Polygon::corridor(&[start, end], half_width);
+		assert_eq!(corridor.len(), 1);
+
+		let expected = 2.0 * half_width * start.distance_to(end) + PI * half_width * half_width;
+		assert_about_eq!(relative_error(corridor[0].area(), expected), 0.0, 1e-2);
+
+		assert!(corridor[0].contains(Coordinates::new(0.05, 1.0)));
+		assert!(corridor[0].contains(Coordinates::new(0.0, 2.05)));
+		assert!(!corridor[0].contains(Coordinates::new(0.1, 1.0)));
+	}
+
+	#[test]
+	fn test_corridor_turn() {
+		let half_width = Length::new::<nautical_mile>(5.0);
+		let corridor = Polygon::corridor(
+			&[
+				Coordinates::new(0.0, 0.0),
+				Coordinates::new(0.0, 1.0),
+				Coordinates::new(1.0, 1.0),
+				Coordinates::new(1.0, 0.0),
+			],
+			half_width,
+		);
+
+		assert_eq!(corridor.len(), 1);
+		assert!(corridor[0].interiors().is_empty());
+		// Round join outside the corner
+		assert!(corridor[0].contains(Coordinates::new(-0.05, 1.05)));
+		assert!(!corridor[0].contains(Coordinates::new(-0.07, 1.07)));
+		assert!(!corridor[0].contains(Coordinates::new(0.5, 0.5)));
+	}
+
+	#[test]
+	fn test_buffer() {
+		let square = Polygon::new(
+			vec![
+				Coordinates::new(0.0, 0.0),
+				Coordinates::new(0.0, 1.0),
+				Coordinates::new(1.0, 1.0),
+				Coordinates::new(1.0, 0.0),
+			],
+			vec![],
+		);
+		let distance = Length::new::<nautical_mile>(6.0);
+		let side = Coordinates::new(0.0, 0.0).distance_to(Coordinates::new(0.0, 1.0));
+
+		let inflated = square.buffer(distance);
+		assert_eq!(inflated.len(), 1);
+		let expected = square.area() + 4.0 * side * distance + PI * distance * distance;
+		assert_about_eq!(relative_error(inflated[0].area(), expected), 0.0, 1e-2);
+
+		let deflated = square.buffer(-distance);
+		assert_eq!(deflated.len(), 1);
+		let expected = (side - 2.0 * distance) * (side - 2.0 * distance);
+		assert_about_eq!(relative_error(deflated[0].area(), expected), 0.0, 1e-2);
+		assert!(deflated[0].contains(Coordinates::new(0.5, 0.5)));
+		assert!(!deflated[0].contains(Coordinates::new(0.05, 0.5)));
+	}
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use uom::si::f64::{Angle, Length, Ratio};
+use uom::si::f64::{Acceleration, Angle, AngularVelocity, Length, Ratio};
 
 pub const EARTH_RADIUS: Length = Length {
 	dimension: PhantomData,
@@ -43,3 +43,17 @@ pub const MAX_LONG: Angle = Angle {
 	units: PhantomData,
 	value: std::f64::consts::PI,
 };
+
+/// Standard acceleration due to gravity at the surface of the Earth
+pub const STANDARD_GRAVITY: Acceleration = Acceleration {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 9.80665,
+};
+
+/// Rate of a standard rate (rate one) turn - 3&deg; per second, or a full turn in two minutes
+pub const STANDARD_RATE_TURN: AngularVelocity = AngularVelocity {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: std::f64::consts::PI / 60.0,
+};
@@ -0,0 +1,203 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::{
+		angle::radian,
+		f64::{Angle, Length},
+	},
+	ConstZero,
+};
+
+use crate::{
+	constants::EARTH_RADIUS,
+	path::Path,
+	segment::{ArcSegment, Segment},
+	Coordinates,
+};
+
+/// Portions of a path shorter than this are ignored, so covered portions meeting at the join between segments are not
+/// separated by a gap
+const COVERAGE_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+/// A portion of a path lying outside the diversion radius of every airport
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CoverageGap {
+	/// Distance along the path to the start of the gap
+	pub start: Length,
+	/// Distance along the path to the end of the gap
+	pub end: Length,
+	/// Point at which the path leaves coverage
+	pub entry: Coordinates,
+	/// Point at which the path returns into coverage
+	pub exit: Coordinates,
+}
+
+impl Path {
+	/// Returns the portions of the path lying further than `radius` from every one of `airports` (e.g. outside the
+	/// ETOPS area of operation), in order along the path
+	pub fn coverage_gaps(&self, airports: &[Coordinates], radius: Length) -> Vec<CoverageGap> {
+		let mut covered: Vec<(Length, Length)> = self
+			.segments()
+			.iter()
+			.enumerate()
+			.flat_map(|(index, segment)| {
+				let offset = self.segment_start_distance(index);
+				airports
+					.iter()
+					.flat_map(move |airport| covered_intervals(segment, *airport, radius))
+					.map(move |(start, end)| (start + offset, end + offset))
+			})
+			.collect();
+		covered.sort_by(|a, b| a.0.value.total_cmp(&b.0.value));
+
+		let mut gaps = Vec::new();
+		let mut reached = Length::ZERO;
+		for (start, end) in covered {
+			if start - reached > COVERAGE_TOLERANCE {
+				gaps.push((reached, start));
+			}
+			reached = reached.max(end);
+		}
+		if self.length() - reached > COVERAGE_TOLERANCE {
+			gaps.push((reached, self.length()));
+		}
+
+		gaps.into_iter()
+			.filter_map(|(start, end)| {
+				Some(CoverageGap {
+					start,
+					end,
+					entry: self.point_at(start)?,
+					exit: self.point_at(end)?,
+				})
+			})
+			.collect()
+	}
+}
+
+/// Returns the intervals of distance along `segment` which lie within `radius` of `airport`
+fn covered_intervals(segment: &Segment, airport: Coordinates, radius: Length) -> Vec<(Length, Length)> {
+	let length = segment.length();
+	let crossings = match segment {
+		Segment::GreatCircle(segment) => {
+			airport.small_circle_great_circle_intersection(radius, segment.start, segment.start_course())
+		},
+		Segment::Arc(arc) => arc_crossings(arc, airport, radius),
+	};
+
+	let (first, second) = match crossings {
+		Some(crossings) => crossings,
+		None if segment.start().distance_to(airport) <= radius => return vec![(Length::ZERO, length)],
+		None => return Vec::new(),
+	};
+
+	let first = segment.along_track_distance(first);
+	let second = segment.along_track_distance(second);
+	let (before, after) = (first.min(second), first.max(second));
+	// The segment only touches the circle
+	if after - before < COVERAGE_TOLERANCE {
+		return Vec::new();
+	}
+
+	// The crossings split the full circle of the segment in two, and either the portion between them or the portion
+	// around the rest of the circle lies within the radius
+	let intervals = if segment.point_at((before + after) / 2.0).distance_to(airport) <= radius {
+		vec![(before, after)]
+	} else {
+		vec![(Length::ZERO, before), (after, length)]
+	};
+
+	intervals
+		.into_iter()
+		.map(|(start, end)| (start.max(Length::ZERO), end.min(length)))
+		.filter(|(start, end)| start < end)
+		.collect()
+}
+
+/// Returns the points where the full circle of `arc` crosses the circle at `radius` around `airport`, or `None` if the
+/// circles do not cross
+fn arc_crossings(arc: &ArcSegment, airport: Coordinates, radius: Length) -> Option<(Coordinates, Coordinates)> {
+	let arc_radius: Angle = (arc.radius / EARTH_RADIUS).into();
+	let diversion_radius: Angle = (radius / EARTH_RADIUS).into();
+	let separation: Angle = (arc.centre.distance_to(airport) / EARTH_RADIUS).into();
+
+	// Spherical law of cosines, for the angle at the centre of the arc between the airport and either crossing
+	let cos_angle =
+		((diversion_radius.cos() - separation.cos() * arc_radius.cos()) / (separation.sin() * arc_radius.sin())).value;
+	if cos_angle.is_nan() || cos_angle.abs() >= 1.0 {
+		return None;
+	}
+
+	let angle = Angle::new::<radian>(cos_angle.acos());
+	let bearing = arc.centre.bearing_to(airport);
+
+	Some((
+		arc.centre.bearing_distance(bearing - angle, arc.radius),
+		arc.centre.bearing_distance(bearing + angle, arc.radius),
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::length::nautical_mile;
+
+	use super::*;
+	use crate::{assert_uom_eq, path::turn_path};
+
+	#[test]
+	fn test_coverage_gaps() {
+		let path = Path::from_waypoints(&[
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.0, 15.0),
+			Coordinates::new(0.0, 30.0),
+		]);
+		let radius = Length::new::<nautical_mile>(300.0);
+		let airports = [Coordinates::new(2.0, 3.0), Coordinates::new(-1.0, 27.0)];
+
+		let gaps = path.coverage_gaps(&airports, radius);
+		assert_eq!(gaps.len(), 1);
+		let gap = gaps[0];
+		assert_uom_eq!(gap.entry.distance_to(airports[0]), radius, 1e-3);
+		assert_uom_eq!(gap.exit.distance_to(airports[1]), radius, 1e-3);
+		assert_uom_eq!(gap.entry.lat, Angle::ZERO, 1e-9);
+		assert_uom_eq!(path.distance_along(gap.exit).unwrap(), gap.end, 1e-3);
+		assert!(gap.start < path.segment_start_distance(1) && gap.end > path.segment_start_distance(1));
+
+		// An airport covering the middle of the route splits the gap in two
+		let airports = [airports[0], airports[1], Coordinates::new(4.0, 15.0)];
+		let gaps = path.coverage_gaps(&airports, radius);
+		assert_eq!(gaps.len(), 2);
+		assert!(gaps[0].end < gaps[1].start);
+
+		let gaps = path.coverage_gaps(&[], radius);
+		assert_eq!(gaps.len(), 1);
+		assert_eq!(gaps[0].start, Length::ZERO);
+		assert_eq!(gaps[0].end, path.length());
+
+		assert!(path
+			.coverage_gaps(&[Coordinates::new(0.0, 15.0)], Length::new::<nautical_mile>(1000.0))
+			.is_empty());
+	}
+
+	#[test]
+	fn test_coverage_gap_in_turn() {
+		let (path, turn) = turn_path(Length::new::<nautical_mile>(5.0));
+		let from = path.segments()[0].start();
+
+		// The path moves steadily away from the airport, leaving coverage halfway around the turn
+		let middle = turn.arc.point_at(turn.arc.length() / 2.0);
+		let gaps = path.coverage_gaps(&[from], from.distance_to(middle));
+		assert_eq!(gaps.len(), 1);
+		assert_uom_eq!(
+			gaps[0].start,
+			path.segment_start_distance(1) + turn.arc.length() / 2.0,
+			1e-3
+		);
+		assert_uom_eq!(gaps[0].entry.distance_to(middle), Length::ZERO, 1e-3);
+		assert_eq!(gaps[0].end, path.length());
+	}
+}
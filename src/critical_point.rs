@@ -0,0 +1,155 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Length, Time, Velocity},
+	ConstZero,
+};
+
+use crate::{
+	path::Path,
+	pseudo_waypoint::{PathDistance, PseudoWaypoint},
+	Coordinates,
+};
+
+/// The equal time point is searched for until it is known to within this distance along the path
+const DISTANCE_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+impl Path {
+	/// Returns the equal time point between the diversion airports `first` and `second`: the point along the path from
+	/// which flying directly to either takes the same time, at `first_ground_speed` towards `first` and
+	/// `second_ground_speed` towards `second`. The ground speeds should account for the wind on each diversion.
+	///
+	/// Returns `None` if the time to `first` is not shorter at the start of the path and longer at the end of it
+	pub fn equal_time_point(
+		&self, first: Coordinates, first_ground_speed: Velocity, second: Coordinates, second_ground_speed: Velocity,
+	) -> Option<PseudoWaypoint> {
+		let time_difference = |distance: Length| -> Option<Time> {
+			let position = self.point_at(distance)?;
+
+			Some(position.distance_to(first) / first_ground_speed - position.distance_to(second) / second_ground_speed)
+		};
+
+		let (mut before, mut after) = (Length::ZERO, self.length());
+		if time_difference(before)? > Time::ZERO || time_difference(after)? < Time::ZERO {
+			return None;
+		}
+
+		while after - before > DISTANCE_TOLERANCE {
+			let middle = (before + after) / 2.0;
+			if time_difference(middle)? > Time::ZERO {
+				after = middle;
+			} else {
+				before = middle;
+			}
+		}
+
+		self.pseudo_waypoint(PathDistance::FromStart((before + after) / 2.0))
+	}
+
+	/// Returns the point of no return for an aircraft with `endurance` at the start of the path: the furthest point
+	/// along the path, at `outbound_ground_speed`, from which it can still return along the path to its start, at
+	/// `homebound_ground_speed`.
+	///
+	/// Returns `None` if the aircraft can fly the whole path and back, so has no point of no return
+	pub fn point_of_no_return(
+		&self, endurance: Time, outbound_ground_speed: Velocity, homebound_ground_speed: Velocity,
+	) -> Option<PseudoWaypoint> {
+		let distance: Length = endurance * outbound_ground_speed * homebound_ground_speed
+			/ (outbound_ground_speed + homebound_ground_speed);
+		if distance >= self.length() {
+			return None;
+		}
+
+		self.pseudo_waypoint(PathDistance::FromStart(distance))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, f64::Angle, length::nautical_mile, time::hour, velocity::knot};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	fn route() -> Path {
+		Path::from_waypoints(&[
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.0, 8.0),
+			Coordinates::new(2.0, 20.0),
+		])
+	}
+
+	#[test]
+	fn test_equal_time_point() {
+		let path = route();
+		let first = Coordinates::new(1.0, 2.0);
+		let second = Coordinates::new(3.0, 18.0);
+
+		let time = |point: &PseudoWaypoint, airport: Coordinates, ground_speed: f64| {
+			point.position.distance_to(airport) / Velocity::new::<knot>(ground_speed)
+		};
+
+		let still_air = path
+			.equal_time_point(
+				first,
+				Velocity::new::<knot>(450.0),
+				second,
+				Velocity::new::<knot>(450.0),
+			)
+			.unwrap();
+		assert_uom_eq!(
+			still_air.position.distance_to(first),
+			still_air.position.distance_to(second),
+			1e-2
+		);
+		assert_uom_eq!(
+			path.point_at(still_air.distance).unwrap().lat,
+			still_air.position.lat,
+			1e-12
+		);
+
+		// A headwind towards the first airport moves the equal time point back towards it
+		let windy = path
+			.equal_time_point(
+				first,
+				Velocity::new::<knot>(400.0),
+				second,
+				Velocity::new::<knot>(500.0),
+			)
+			.unwrap();
+		assert!(windy.distance < still_air.distance);
+		assert_uom_eq!(time(&windy, first, 400.0), time(&windy, second, 500.0), 1e-2);
+
+		// Both airports lie ahead of the end of the path
+		assert!(path
+			.equal_time_point(
+				Coordinates::new(2.0, 25.0),
+				Velocity::new::<knot>(450.0),
+				Coordinates::new(2.0, 30.0),
+				Velocity::new::<knot>(450.0)
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn test_point_of_no_return() {
+		let path = route();
+		let outbound = Velocity::new::<knot>(400.0);
+		let homebound = Velocity::new::<knot>(500.0);
+		let endurance = Time::new::<hour>(4.0);
+
+		let point = path.point_of_no_return(endurance, outbound, homebound).unwrap();
+		assert_uom_eq!(point.distance, Length::new::<nautical_mile>(4000.0 / 4.5), 1e-6);
+		assert_uom_eq!(point.distance / outbound + point.distance / homebound, endurance, 1e-9);
+		assert_eq!(point.segment, 1);
+		assert!(point.course > Angle::ZERO && point.course < Angle::new::<degree>(90.0));
+
+		assert!(path
+			.point_of_no_return(Time::new::<hour>(8.0), outbound, homebound)
+			.is_none());
+	}
+}
@@ -0,0 +1,73 @@
+use uom::si::f64::{Angle, Length};
+
+use crate::{constants::EARTH_RADIUS, Coordinates};
+
+impl Coordinates {
+	/// Returns the distance of `self` from the great circle passing through `start` on `course`. The distance is
+	/// positive when `self` is to the right of the great circle, and negative when it is to the left
+	pub fn cross_track_distance(self, start: Coordinates, course: Angle) -> Length {
+		let radial_distance: Angle = (start.distance_to(self) / EARTH_RADIUS).into();
+		let delta_course = start.bearing_to(self) - course;
+
+		let cross_track: Angle = (radial_distance.sin() * delta_course.sin()).asin();
+
+		EARTH_RADIUS * cross_track.value
+	}
+
+	/// Returns the distance from `start`, along the great circle passing through `start` on `course`, to the point on
+	/// that great circle closest to `self`. The distance is negative when that point is behind `start`
+	pub fn along_track_distance(self, start: Coordinates, course: Angle) -> Length {
+		let radial_distance: Angle = (start.distance_to(self) / EARTH_RADIUS).into();
+		let delta_course = start.bearing_to(self) - course;
+
+		let along_track: Angle = (radial_distance.sin() * delta_course.cos()).atan2(radial_distance.cos());
+
+		EARTH_RADIUS * along_track.value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::{
+		si::{angle::degree, length::nautical_mile},
+		ConstZero,
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_cross_track_distance() {
+		let start = Coordinates::new(0.0, 0.0);
+
+		let one_degree = EARTH_RADIUS * Angle::new::<degree>(1.0).value;
+
+		let distance = Coordinates::new(1.0, 1.0).cross_track_distance(start, Angle::HALF_TURN / 2.0);
+		assert_uom_eq!(distance, -one_degree, 1e-3);
+
+		let distance = Coordinates::new(-1.0, 1.0).cross_track_distance(start, Angle::HALF_TURN / 2.0);
+		assert_uom_eq!(distance, one_degree, 1e-3);
+
+		let distance = Coordinates::new(0.0, -179.0).cross_track_distance(Coordinates::new(0.0, 179.0), Angle::ZERO);
+		assert_uom_eq!(distance, 2.0 * one_degree, 1e-3);
+	}
+
+	#[test]
+	fn test_along_track_distance() {
+		let start = Coordinates::new(0.0, 179.0);
+		let course = Angle::new::<degree>(90.0);
+
+		let point = start.bearing_distance(course, Length::new::<nautical_mile>(100.0));
+		let distance = point
+			.bearing_distance(course + Angle::HALF_TURN / 2.0, Length::new::<nautical_mile>(10.0))
+			.along_track_distance(start, course);
+		assert_uom_eq!(distance, Length::new::<nautical_mile>(100.0), 1e-3);
+
+		let point = start.bearing_distance(course + Angle::HALF_TURN, Length::new::<nautical_mile>(50.0));
+		assert_uom_eq!(
+			point.along_track_distance(start, course),
+			Length::new::<nautical_mile>(-50.0),
+			1e-3
+		);
+	}
+}
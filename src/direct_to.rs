@@ -0,0 +1,218 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::{
+		angle::radian,
+		f64::{Angle, Length},
+	},
+	ConstZero,
+};
+
+use crate::{
+	constants::EARTH_RADIUS,
+	path::Path,
+	segment::{quarter_turn, turn_direction, ArcSegment, GreatCircleSegment},
+	utility::diff_angle,
+	Coordinates,
+	Direction,
+};
+
+/// Below this angle between the current track and the bearing to the fix, the aircraft is treated as already tracking
+/// directly to the fix
+const ON_TRACK_ANGLE: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-9,
+};
+
+impl Coordinates {
+	/// Returns the direct to path from `self` on `track` to `fix`: a turn at `radius` in `direction`, followed by the
+	/// great circle from the end of the turn to `fix`. When `direction` is `Either`, the turn giving the shorter path
+	/// is chosen.
+	///
+	/// Returns `None` if `fix` is inside the turn
+	pub fn direct_to(self, track: Angle, fix: Coordinates, radius: Length, direction: Direction) -> Option<Path> {
+		if diff_angle(track, self.bearing_to(fix), Direction::Either).abs() < ON_TRACK_ANGLE {
+			return Some(Path::from_waypoints(&[self, fix]));
+		}
+
+		let path = |direction: Direction| -> Option<Path> {
+			let turn = self.turn_tangent_to(track, fix, radius, direction)?;
+
+			let mut path = Path::new();
+			path.push_waypoint();
+			path.push(turn);
+			path.push(GreatCircleSegment::new(turn.end, fix));
+			path.push_waypoint();
+			Some(path)
+		};
+
+		match direction {
+			Direction::Either => match (path(Direction::Left), path(Direction::Right)) {
+				(Some(left), Some(right)) if left.length() < right.length() => Some(left),
+				(Some(_), Some(right)) => Some(right),
+				(left, right) => left.or(right),
+			},
+			_ => path(direction),
+		}
+	}
+
+	/// Returns the direct to path from `self` on `track` to `fix`, joining `inbound_course` to the fix at
+	/// `intercept_angle` after a turn at `radius` in `direction`. When `direction` is `Either`, the shorter turn onto
+	/// the inbound course is chosen.
+	///
+	/// Returns `None` if the inbound course cannot be joined before `fix`
+	pub fn direct_to_course(
+		self, track: Angle, fix: Coordinates, inbound_course: Angle, radius: Length, direction: Direction,
+		intercept_angle: Angle,
+	) -> Option<Path> {
+		let transition = self.turn_to_intercept(track, radius, direction, fix, inbound_course, intercept_angle)?;
+		let join = transition.intercept.end;
+		if join.along_track_distance(fix, inbound_course) > Length::ZERO {
+			return None;
+		}
+
+		let mut path = Path::new();
+		path.push_waypoint();
+		path.push(transition.turn);
+		path.push(transition.intercept);
+		path.push(GreatCircleSegment::new(join, fix));
+		path.push_waypoint();
+		Some(path)
+	}
+
+	/// Returns the turn from `self` on `track` at `radius` in `direction` (`Left` or `Right`), ending where the great
+	/// circle to `fix` leaves the turn at a tangent
+	fn turn_tangent_to(
+		self, track: Angle, fix: Coordinates, radius: Length, direction: Direction,
+	) -> Option<ArcSegment> {
+		let direction = turn_direction(direction);
+		let centre = self.bearing_distance(track + quarter_turn(direction), radius);
+
+		let turn_radius: Angle = (radius / EARTH_RADIUS).into();
+		let fix_distance: Angle = (centre.distance_to(fix) / EARTH_RADIUS).into();
+		if fix_distance <= turn_radius {
+			return None;
+		}
+
+		// The great circle to the fix meets the radial to the tangent point at a right angle
+		let tangent_angle = Angle::new::<radian>((turn_radius.tan() / fix_distance.tan()).value.acos());
+		let fix_radial = centre.bearing_to(fix);
+		let tangent_radial = match direction {
+			Direction::Left => fix_radial + tangent_angle,
+			_ => fix_radial - tangent_angle,
+		};
+
+		Some(ArcSegment {
+			centre,
+			radius,
+			start: self,
+			end: centre.bearing_distance(tangent_radial, radius),
+			direction,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::{assert_uom_eq, segment::Segment};
+
+	fn assert_tangent(path: &Path, fix: Coordinates) {
+		let segments = path.segments();
+		for pair in segments.windows(2) {
+			assert!(pair[0].end().distance_to(pair[1].start()) < Length::new::<nautical_mile>(1e-6));
+			// Turns end tangent to the following segment
+			if let Segment::Arc(arc) = pair[0] {
+				assert_uom_eq!(
+					diff_angle(arc.end_course(), pair[1].course_at(pair[1].start()), Direction::Either),
+					Angle::ZERO,
+					1e-5
+				);
+			}
+		}
+		assert_eq!(segments[segments.len() - 1].end(), fix);
+	}
+
+	#[test]
+	fn test_direct_to() {
+		let position = Coordinates::new(0.0, 0.0);
+		let fix = Coordinates::new(0.0, 1.0);
+		let radius = Length::new::<nautical_mile>(3.0);
+
+		let path = position.direct_to(Angle::ZERO, fix, radius, Direction::Either).unwrap();
+		assert_tangent(&path, fix);
+		assert_eq!(path.waypoint_distances().len(), 2);
+		match path.segments()[0] {
+			Segment::Arc(arc) => {
+				assert_eq!(arc.direction, Direction::Right);
+				// A little more than a quarter turn, rolling out slightly right of east as the fix is right of the turn
+				assert!(arc.sweep() > Angle::new::<degree>(90.0) && arc.sweep() < Angle::new::<degree>(95.0));
+			},
+			_ => panic!(),
+		}
+
+		let left = position.direct_to(Angle::ZERO, fix, radius, Direction::Left).unwrap();
+		assert_tangent(&left, fix);
+		assert!(left.length() > path.length());
+
+		let straight = position
+			.direct_to(position.bearing_to(fix), fix, radius, Direction::Either)
+			.unwrap();
+		assert_eq!(straight.segments().len(), 1);
+
+		// The fix is inside a right turn
+		let close = position.bearing_distance(Angle::new::<degree>(90.0), Length::new::<nautical_mile>(1.0));
+		assert!(position
+			.direct_to(Angle::ZERO, close, radius, Direction::Right)
+			.is_none());
+	}
+
+	#[test]
+	fn test_direct_to_course() {
+		let position = Coordinates::new(0.5, 0.0);
+		let fix = Coordinates::new(0.0, 1.0);
+		let radius = Length::new::<nautical_mile>(3.0);
+		let inbound_course = Angle::new::<degree>(90.0);
+
+		let path = position
+			.direct_to_course(
+				Angle::new::<degree>(90.0),
+				fix,
+				inbound_course,
+				radius,
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+		assert_tangent(&path, fix);
+
+		let segments = path.segments();
+		assert_eq!(segments.len(), 3);
+		assert_uom_eq!(segments[2].course_at(segments[2].end()), inbound_course, 1e-6);
+		assert_uom_eq!(
+			diff_angle(
+				inbound_course,
+				segments[1].course_at(segments[1].start()),
+				Direction::Either
+			),
+			Angle::new::<degree>(45.0),
+			1e-6
+		);
+
+		// Too close to the fix to join the inbound course before it
+		let position = Coordinates::new(0.5, 0.95);
+		assert!(position
+			.direct_to_course(
+				Angle::new::<degree>(90.0),
+				fix,
+				inbound_course,
+				radius,
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.is_none());
+	}
+}
@@ -15,7 +15,7 @@ impl Coordinates {
 
 		let c = 2.0 * a.sqrt().atan2((FULL_RATIO - a).sqrt());
 
-		return EARTH_RADIUS * c;
+		EARTH_RADIUS * c
 	}
 }
 
@@ -0,0 +1,277 @@
+use std::f64::consts::PI;
+
+use uom::{
+	si::{
+		angle::radian,
+		f64::{Angle, Curvature, Length, Ratio, Time, Velocity},
+	},
+	ConstZero,
+};
+
+use crate::{
+	constants::{EARTH_RADIUS, STANDARD_GRAVITY},
+	kinematics::bank_for_radius,
+	path::Path,
+	segment::Segment,
+	utility::diff_angle,
+	Coordinates,
+	Direction,
+};
+
+/// Lateral navigation outputs for an aircraft flying a path
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LateralGuidance {
+	/// Index of the segment of the path the guidance is computed against
+	pub active_segment: usize,
+	/// Distance of the aircraft from the active segment - positive when right of it, or negative when left
+	pub cross_track_error: Length,
+	/// Course of the active segment abeam the aircraft
+	pub desired_track: Angle,
+	/// Angle from the desired track to the ground track - positive when tracking right of it, or negative when left
+	pub track_angle_error: Angle,
+	/// Curvature of the active segment - positive when it turns right, negative when it turns left, or zero when it is
+	/// straight
+	pub curvature: Curvature,
+	/// Bank angle needed to follow the curvature of the active segment at the aircraft's ground speed, with the same
+	/// sign as `curvature`
+	pub feed_forward_bank: Angle,
+}
+
+/// An L1 path following guidance law, which steers towards a reference point a fixed distance ahead on the path, with
+/// the bank needed to follow the curvature of the path added as a feed-forward term
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct L1Guidance {
+	/// Period of the oscillation when converging onto a straight path
+	pub period: Time,
+	/// Damping ratio when converging onto a straight path
+	pub damping: Ratio,
+	/// Largest bank angle which may be commanded either way
+	pub max_bank: Angle,
+}
+
+impl L1Guidance {
+	/// Returns the distance ahead of the aircraft to the reference point on the path at `ground_speed`
+	pub fn l1_distance(&self, ground_speed: Velocity) -> Length { self.damping * self.period * ground_speed / PI }
+
+	/// Returns the bank angle to command for `guidance` at `ground_speed` - positive to the right, or negative to the
+	/// left
+	pub fn roll_command(&self, guidance: &LateralGuidance, ground_speed: Velocity) -> Angle {
+		let l1_distance = self.l1_distance(ground_speed);
+		let capture = if l1_distance > Length::ZERO {
+			Angle::new::<radian>((guidance.cross_track_error / l1_distance).value.clamp(-1.0, 1.0).asin())
+		} else {
+			Angle::ZERO
+		};
+
+		// Angle from the ground track to the reference point, limited so the aircraft never turns away from the path
+		let eta = (-(capture + guidance.track_angle_error))
+			.max(-Angle::HALF_TURN / 2.0)
+			.min(Angle::HALF_TURN / 2.0);
+		let lateral_acceleration = 2.0 * PI * ground_speed * eta.sin() / (self.damping * self.period);
+
+		let bank = (lateral_acceleration / STANDARD_GRAVITY).atan() + guidance.feed_forward_bank;
+		bank.max(-self.max_bank).min(self.max_bank)
+	}
+}
+
+impl Path {
+	/// Returns the lateral guidance for an aircraft at `position` on `track` at `ground_speed`, against the segment of
+	/// the path closest to it. Returns `None` if the path is empty
+	pub fn lateral_guidance(
+		&self, position: Coordinates, track: Angle, ground_speed: Velocity,
+	) -> Option<LateralGuidance> {
+		let index = self.nearest_segment(position)?;
+
+		self.segment_guidance(index, position, track, ground_speed)
+	}
+
+	/// Returns the lateral guidance for an aircraft at `position` on `track` at `ground_speed`, against the segment of
+	/// the path at `index`. Returns `None` if there is no such segment
+	pub fn segment_guidance(
+		&self, index: usize, position: Coordinates, track: Angle, ground_speed: Velocity,
+	) -> Option<LateralGuidance> {
+		let segment = self.segments().get(index)?;
+		let abeam = segment.point_at(segment.along_track_distance(position));
+		let desired_track = segment.course_at(abeam);
+
+		let (curvature, feed_forward_bank) = match segment {
+			Segment::GreatCircle(_) => (Curvature::ZERO, Angle::ZERO),
+			Segment::Arc(arc) => {
+				// Radius of the arc measured across the surface of the planet's tangent plane
+				let radial_distance: Angle = (arc.radius / EARTH_RADIUS).into();
+				let radius = EARTH_RADIUS * radial_distance.sin();
+				let curvature: Curvature = (Angle::new::<radian>(1.0) / radius).into();
+				let bank = bank_for_radius(ground_speed, radius);
+
+				match arc.turn_direction() {
+					Direction::Left => (-curvature, -bank),
+					_ => (curvature, bank),
+				}
+			},
+		};
+
+		Some(LateralGuidance {
+			active_segment: index,
+			cross_track_error: segment.cross_track_distance(position),
+			desired_track,
+			track_angle_error: diff_angle(desired_track, track, Direction::Either),
+			curvature,
+			feed_forward_bank,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{
+		angle::degree,
+		curvature::radian_per_meter,
+		length::{meter, nautical_mile},
+		ratio::ratio,
+		time::second,
+		velocity::{knot, meter_per_second},
+	};
+
+	use super::*;
+	use crate::{assert_uom_eq, kinematics::turn_radius, segment::ArcSegment};
+
+	#[test]
+	fn test_great_circle_guidance() {
+		let path = Path::from_waypoints(&[
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.0, 1.0),
+			Coordinates::new(1.0, 1.0),
+		]);
+		let position = Coordinates::new(0.6, 1.02);
+
+		let guidance = path
+			.lateral_guidance(position, Angle::new::<degree>(350.0), Velocity::new::<knot>(250.0))
+			.unwrap();
+		assert_eq!(guidance.active_segment, 1);
+		assert_uom_eq!(
+			guidance.cross_track_error,
+			EARTH_RADIUS * Angle::new::<degree>(0.02).value * Angle::new::<degree>(0.6).cos().value,
+			1.0
+		);
+		assert_uom_eq!(guidance.desired_track, Angle::ZERO, 1e-9);
+		assert_uom_eq!(guidance.track_angle_error, Angle::new::<degree>(-10.0), 1e-9);
+		assert_eq!(guidance.curvature, Curvature::ZERO);
+		assert_eq!(guidance.feed_forward_bank, Angle::ZERO);
+
+		assert!(path
+			.segment_guidance(2, position, Angle::ZERO, Velocity::new::<knot>(250.0))
+			.is_none());
+	}
+
+	#[test]
+	fn test_arc_guidance() {
+		let centre = Coordinates::new(45.0, 7.0);
+		let radius = Length::new::<nautical_mile>(3.0);
+		let mut path = Path::new();
+		path.push(ArcSegment {
+			centre,
+			radius,
+			start: centre.bearing_distance(Angle::new::<degree>(90.0), radius),
+			end: centre.bearing_distance(Angle::ZERO, radius),
+			direction: Direction::Left,
+		});
+
+		let position = centre.bearing_distance(Angle::new::<degree>(45.0), Length::new::<nautical_mile>(2.8));
+		let ground_speed = Velocity::new::<knot>(180.0);
+		let guidance = path
+			.lateral_guidance(position, Angle::new::<degree>(300.0), ground_speed)
+			.unwrap();
+
+		// Inside a left turn is to the left of the path
+		assert_uom_eq!(guidance.cross_track_error, Length::new::<nautical_mile>(-0.2), 1e-3);
+		// Tangent to the arc, roughly 315 once meridian convergence is accounted for
+		let abeam = centre.bearing_distance(Angle::new::<degree>(45.0), radius);
+		let desired_track = abeam.bearing_to(centre) + Angle::HALF_TURN / 2.0;
+		assert_uom_eq!(guidance.desired_track, desired_track, 1e-6);
+		assert_uom_eq!(
+			guidance.track_angle_error,
+			Angle::new::<degree>(300.0) - desired_track,
+			1e-6
+		);
+		assert_uom_eq!(
+			guidance.curvature,
+			Curvature::new::<radian_per_meter>(-1.0 / radius.value),
+			1e-9
+		);
+
+		assert_uom_eq!(guidance.feed_forward_bank, -bank_for_radius(ground_speed, radius), 1e-4);
+		assert_uom_eq!(turn_radius(ground_speed, -guidance.feed_forward_bank), radius, 1.0);
+	}
+
+	#[test]
+	fn test_l1_roll_command() {
+		let law = L1Guidance {
+			period: Time::new::<second>(20.0),
+			damping: Ratio::new::<ratio>(0.75),
+			max_bank: Angle::new::<degree>(25.0),
+		};
+		let ground_speed = Velocity::new::<meter_per_second>(120.0);
+		let l1_distance = law.l1_distance(ground_speed);
+		assert_uom_eq!(l1_distance, Length::new::<meter>(0.75 * 20.0 * 120.0 / PI), 1e-9);
+
+		let on_track = LateralGuidance {
+			active_segment: 0,
+			cross_track_error: Length::ZERO,
+			desired_track: Angle::ZERO,
+			track_angle_error: Angle::ZERO,
+			curvature: Curvature::ZERO,
+			feed_forward_bank: Angle::ZERO,
+		};
+		assert_uom_eq!(law.roll_command(&on_track, ground_speed), Angle::ZERO, 1e-12);
+
+		// Slightly right of the path, so steer left towards it
+		let right = LateralGuidance {
+			cross_track_error: Length::new::<meter>(10.0),
+			..on_track
+		};
+		let acceleration = 2.0 * ground_speed * ground_speed / l1_distance * (-(10.0 / l1_distance.value).asin()).sin();
+		assert_uom_eq!(
+			law.roll_command(&right, ground_speed),
+			(acceleration / STANDARD_GRAVITY).atan(),
+			1e-9
+		);
+
+		// Far left of the path, so the command is limited
+		let far_left = LateralGuidance {
+			cross_track_error: Length::new::<meter>(-5000.0),
+			..on_track
+		};
+		assert_uom_eq!(law.roll_command(&far_left, ground_speed), law.max_bank, 1e-12);
+
+		// Already converging on the path at the ideal angle
+		let converging = LateralGuidance {
+			cross_track_error: Length::new::<meter>(10.0),
+			track_angle_error: -Angle::new::<radian>((10.0 / l1_distance.value).asin()),
+			..on_track
+		};
+		assert_uom_eq!(law.roll_command(&converging, ground_speed), Angle::ZERO, 1e-12);
+	}
+
+	#[test]
+	fn test_l1_roll_command_on_arc() {
+		let law = L1Guidance {
+			period: Time::new::<second>(20.0),
+			damping: Ratio::new::<ratio>(0.75),
+			max_bank: Angle::new::<degree>(25.0),
+		};
+		let guidance = LateralGuidance {
+			active_segment: 0,
+			cross_track_error: Length::ZERO,
+			desired_track: Angle::ZERO,
+			track_angle_error: Angle::ZERO,
+			curvature: Curvature::new::<radian_per_meter>(-1e-4),
+			feed_forward_bank: Angle::new::<degree>(-15.0),
+		};
+
+		assert_uom_eq!(
+			law.roll_command(&guidance, Velocity::new::<knot>(180.0)),
+			Angle::new::<degree>(-15.0),
+			1e-12
+		);
+	}
+}
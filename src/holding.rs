@@ -0,0 +1,548 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::{
+		angle::{degree, radian},
+		f64::{Angle, Length, Time, Velocity},
+		time::second,
+	},
+	ConstZero,
+};
+
+use crate::{
+	kinematics::turn_radius,
+	segment::{quarter_turn, turn_direction, ArcSegment, GreatCircleSegment, Segment},
+	utility::{clamp_angle_cw, diff_angle},
+	wind::Wind,
+	Coordinates,
+	Direction,
+	Direction::Either,
+};
+
+/// Angle between the teardrop entry outbound course and the hold outbound course
+const TEARDROP_ANGLE: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: std::f64::consts::FRAC_PI_6,
+};
+
+/// Change of heading, in degrees, between the points sampled along the turns of a wind corrected hold
+const TURN_SAMPLE_STEP: f64 = 5.0;
+
+/// Angle at which the parallel and teardrop entries intercept the inbound course
+const ENTRY_INTERCEPT_ANGLE: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: std::f64::consts::FRAC_PI_4,
+};
+
+/// The procedure used to enter a hold, based on the track of the aircraft as it arrives at the holding fix
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HoldEntry {
+	Direct,
+	Parallel,
+	Teardrop,
+}
+
+/// The length of the straight legs of a hold
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum HoldLeg {
+	Time(Time),
+	Distance(Length),
+}
+
+/// A holding pattern, with its inbound leg ending at `fix`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hold {
+	pub fix: Coordinates,
+	/// The course of the inbound leg as it arrives at the fix
+	pub inbound_course: Angle,
+	/// The direction of the turns - `Left` or `Right`. **Note** that `Either` is treated as `Right`
+	pub direction: Direction,
+	pub leg: HoldLeg,
+}
+
+/// The still air ground path of one circuit of a hold, starting and ending at the fix
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Racetrack {
+	/// The turn from the fix onto the outbound leg
+	pub outbound_turn: ArcSegment,
+	pub outbound: GreatCircleSegment,
+	/// The turn from the outbound leg onto the inbound leg
+	pub inbound_turn: ArcSegment,
+	pub inbound: GreatCircleSegment,
+}
+
+/// The predicted ground path of a hold flown at a constant true airspeed and bank angle in a steady wind
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindCorrectedHold {
+	/// The heading flown on the inbound leg to track the inbound course
+	pub inbound_heading: Angle,
+	/// The heading flown on the outbound leg, so that the turn inbound rolls out on the inbound course
+	pub outbound_heading: Angle,
+	/// The time flown on the outbound heading, so that the inbound leg has the correct length
+	pub outbound_time: Time,
+	/// The predicted ground track of one circuit, starting and ending at the fix
+	pub ground_track: Vec<Coordinates>,
+}
+
+impl Hold {
+	/// Returns the entry procedure for an aircraft arriving at the fix on `track`
+	pub fn entry(&self, track: Angle) -> HoldEntry {
+		let relative_track = match self.direction {
+			Direction::Left => diff_angle(self.inbound_course, track, Direction::Left).abs(),
+			_ => diff_angle(self.inbound_course, track, Direction::Right),
+		};
+		let degrees = relative_track.get::<degree>();
+
+		if !(110.0..290.0).contains(&degrees) {
+			HoldEntry::Direct
+		} else if degrees < 180.0 {
+			HoldEntry::Teardrop
+		} else {
+			HoldEntry::Parallel
+		}
+	}
+
+	/// Returns the length of the straight legs of the hold. `ground_speed` is used to convert timed legs to a distance
+	pub fn leg_length(&self, ground_speed: Velocity) -> Length {
+		match self.leg {
+			HoldLeg::Time(time) => ground_speed * time,
+			HoldLeg::Distance(distance) => distance,
+		}
+	}
+
+	/// Returns the still air racetrack flown with turns at `radius`, at `ground_speed`
+	pub fn racetrack(&self, radius: Length, ground_speed: Velocity) -> Racetrack {
+		let length = self.leg_length(ground_speed);
+		let direction = turn_direction(self.direction);
+		let abeam = quarter_turn(self.direction);
+
+		let inbound_start = self
+			.fix
+			.bearing_distance(self.inbound_course + Angle::HALF_TURN, length);
+		let inbound_start_course = inbound_start.bearing_to(self.fix);
+
+		let outbound_start = self.fix.bearing_distance(self.inbound_course + abeam, 2.0 * radius);
+		let outbound_end = inbound_start.bearing_distance(inbound_start_course + abeam, 2.0 * radius);
+
+		Racetrack {
+			outbound_turn: ArcSegment {
+				centre: self.fix.bearing_distance(self.inbound_course + abeam, radius),
+				radius,
+				start: self.fix,
+				end: outbound_start,
+				direction,
+			},
+			outbound: GreatCircleSegment::new(outbound_start, outbound_end),
+			inbound_turn: ArcSegment {
+				centre: inbound_start.bearing_distance(inbound_start_course + abeam, radius),
+				radius,
+				start: outbound_end,
+				end: inbound_start,
+				direction,
+			},
+			inbound: GreatCircleSegment::new(inbound_start, self.fix),
+		}
+	}
+
+	/// Returns the entry procedure, and its ground path, for an aircraft arriving at the fix on `track`, with turns at
+	/// `radius` and at `ground_speed`.
+	///
+	/// The direct entry path turns onto the outbound leg of the racetrack. The parallel and teardrop entry paths end
+	/// back at the fix, after which the racetrack is flown. Returns `None` if the entry cannot intercept the leg it
+	/// joins
+	pub fn entry_path(
+		&self, track: Angle, radius: Length, ground_speed: Velocity,
+	) -> Option<(HoldEntry, Vec<Segment>)> {
+		let entry = self.entry(track);
+		let direction = turn_direction(self.direction);
+		let outbound_course = self.inbound_course + Angle::HALF_TURN;
+		let mut path: Vec<Segment> = Vec::new();
+
+		let (course, first_turn, return_direction) = match entry {
+			HoldEntry::Direct => {
+				let racetrack = self.racetrack(radius, ground_speed);
+				let transition = self.fix.turn_to_intercept(
+					track,
+					radius,
+					direction,
+					racetrack.outbound.start,
+					racetrack.outbound.start_course(),
+					ENTRY_INTERCEPT_ANGLE,
+				)?;
+				path.push(transition.turn.into());
+				path.push(transition.intercept.into());
+				return Some((entry, path));
+			},
+			HoldEntry::Parallel => {
+				let opposite = match direction {
+					Direction::Left => Direction::Right,
+					_ => Direction::Left,
+				};
+				(outbound_course, opposite, opposite)
+			},
+			HoldEntry::Teardrop => {
+				// The teardrop outbound course points towards the holding side
+				let course = match direction {
+					Direction::Left => outbound_course + TEARDROP_ANGLE,
+					_ => outbound_course - TEARDROP_ANGLE,
+				};
+				(course, Direction::Either, direction)
+			},
+		};
+
+		let turn = self.fix.turn_to_course(track, course, radius, first_turn);
+		let outbound = GreatCircleSegment::new(
+			turn.end,
+			turn.end.bearing_distance(course, self.leg_length(ground_speed)),
+		);
+		path.push(turn.into());
+		path.push(outbound.into());
+
+		let transition = outbound.end.turn_to_intercept(
+			outbound.end_course(),
+			radius,
+			return_direction,
+			self.fix,
+			self.inbound_course,
+			ENTRY_INTERCEPT_ANGLE,
+		)?;
+		path.push(transition.turn.into());
+		path.push(transition.intercept.into());
+		path.push(GreatCircleSegment::new(transition.intercept.end, self.fix).into());
+
+		Some((entry, path))
+	}
+
+	/// Returns the path of the hold flown at `true_airspeed` with turns at `bank` angle, in `wind`.
+	///
+	/// The turns drift with the wind, and the outbound heading and time are corrected so that the turn inbound rolls
+	/// out on the inbound course, with the inbound leg taking the hold leg time or distance. Returns `None` if the wind
+	/// is too strong for the hold to be flown
+	pub fn wind_corrected_path(&self, true_airspeed: Velocity, bank: Angle, wind: Wind) -> Option<WindCorrectedHold> {
+		let (inbound_heading, inbound_ground_speed) = wind.heading_for_track(self.inbound_course, true_airspeed)?;
+		let inbound_length = self.leg_length(inbound_ground_speed);
+
+		let turns = DriftingTurns {
+			radius: turn_radius(true_airspeed, bank),
+			true_airspeed,
+			wind: wind.vector(),
+			direction: turn_direction(self.direction),
+		};
+		let target = (
+			-inbound_length * self.inbound_course.sin(),
+			-inbound_length * self.inbound_course.cos(),
+		);
+
+		let mut outbound_heading =
+			self.inbound_course + Angle::HALF_TURN - 3.0 * diff_angle(self.inbound_course, inbound_heading, Either);
+		let mut outbound_time = match self.leg {
+			HoldLeg::Time(time) => time,
+			HoldLeg::Distance(distance) => distance / true_airspeed,
+		};
+
+		let error = |heading: Angle, time: Time| {
+			let end = turns.circuit_end(inbound_heading, heading, time);
+			((end.0 - target.0).value, (end.1 - target.1).value)
+		};
+
+		for _ in 0..50 {
+			let (x, y) = error(outbound_heading, outbound_time);
+			if x.hypot(y) < 1e-3 {
+				break;
+			}
+
+			// Newton's method, with the Jacobian estimated by finite differences
+			let heading_step = Angle::new::<radian>(1e-6);
+			let time_step = Time::new::<second>(1e-3);
+			let (x_heading, y_heading) = error(outbound_heading + heading_step, outbound_time);
+			let (x_time, y_time) = error(outbound_heading, outbound_time + time_step);
+
+			let a = (x_heading - x) / heading_step.value;
+			let b = (x_time - x) / time_step.value;
+			let c = (y_heading - y) / heading_step.value;
+			let d = (y_time - y) / time_step.value;
+			let determinant = a * d - b * c;
+			if determinant.abs() < 1e-12 {
+				return None;
+			}
+
+			outbound_heading -= Angle::new::<radian>((d * x - b * y) / determinant);
+			outbound_time -= Time::new::<second>((a * y - c * x) / determinant);
+		}
+
+		let (x, y) = error(outbound_heading, outbound_time);
+		if x.hypot(y) > 1.0 || outbound_time < Time::ZERO {
+			return None;
+		}
+
+		let mut ground_track = Vec::new();
+		let mut position = (Length::ZERO, Length::ZERO);
+		position = turns.sample(position, inbound_heading, outbound_heading, &mut ground_track);
+		position = turns.straight(position, outbound_heading, outbound_time);
+		turns.sample(position, outbound_heading, inbound_heading, &mut ground_track);
+
+		Some(WindCorrectedHold {
+			inbound_heading,
+			outbound_heading: clamp_angle_cw(outbound_heading),
+			outbound_time,
+			ground_track: std::iter::once(self.fix)
+				.chain(
+					ground_track
+						.into_iter()
+						.skip(1)
+						.map(|(east, north)| self.fix.bearing_distance(east.atan2(north), east.hypot(north))),
+				)
+				.chain(std::iter::once(self.fix))
+				.collect(),
+		})
+	}
+}
+
+/// Turns flown at a constant true airspeed and bank angle while drifting with the wind, in a flat frame of east and
+/// north distances from the holding fix
+struct DriftingTurns {
+	/// Still air turn radius
+	radius: Length,
+	true_airspeed: Velocity,
+	/// East and north components of the motion of the air mass
+	wind: (Velocity, Velocity),
+	direction: Direction,
+}
+
+impl DriftingTurns {
+	/// Returns the position after turning from `heading` through `change`, starting at `position`
+	fn turn(&self, position: (Length, Length), heading: Angle, change: Angle) -> (Length, Length) {
+		let sign = if change < Angle::ZERO { -1.0 } else { 1.0 };
+		let new_heading = heading + change;
+		let time = self.radius * change.abs().value / self.true_airspeed;
+
+		(
+			position.0 + sign * self.radius * (heading.cos() - new_heading.cos()) + self.wind.0 * time,
+			position.1 + sign * self.radius * (new_heading.sin() - heading.sin()) + self.wind.1 * time,
+		)
+	}
+
+	/// Returns the position after flying `heading` for `time`, starting at `position`
+	fn straight(&self, position: (Length, Length), heading: Angle, time: Time) -> (Length, Length) {
+		(
+			position.0 + (self.true_airspeed * heading.sin() + self.wind.0) * time,
+			position.1 + (self.true_airspeed * heading.cos() + self.wind.1) * time,
+		)
+	}
+
+	/// Returns the position at the end of the turn inbound, after turning from the fix onto `outbound_heading` and
+	/// holding it for `outbound_time`
+	fn circuit_end(&self, inbound_heading: Angle, outbound_heading: Angle, outbound_time: Time) -> (Length, Length) {
+		let position = self.turn(
+			(Length::ZERO, Length::ZERO),
+			inbound_heading,
+			diff_angle(inbound_heading, outbound_heading, self.direction),
+		);
+		let position = self.straight(position, outbound_heading, outbound_time);
+
+		self.turn(
+			position,
+			outbound_heading,
+			diff_angle(outbound_heading, inbound_heading, self.direction),
+		)
+	}
+
+	/// Appends points along the turn from `heading` to `new_heading` to `points`, returning the end of the turn
+	fn sample(
+		&self, position: (Length, Length), heading: Angle, new_heading: Angle, points: &mut Vec<(Length, Length)>,
+	) -> (Length, Length) {
+		let change = diff_angle(heading, new_heading, self.direction);
+		let steps = (change.abs().get::<degree>() / TURN_SAMPLE_STEP).ceil().max(1.0) as usize;
+
+		for i in 0..steps {
+			points.push(self.turn(position, heading, change * (i as f64 / steps as f64)));
+		}
+
+		let end = self.turn(position, heading, change);
+		points.push(end);
+		end
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use uom::si::{length::nautical_mile, time::minute, velocity::knot};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	fn hold(direction: Direction) -> Hold {
+		Hold {
+			fix: Coordinates::new(45.0, -179.9),
+			inbound_course: Angle::new::<degree>(360.0),
+			direction,
+			leg: HoldLeg::Time(Time::new::<minute>(1.0)),
+		}
+	}
+
+	#[test]
+	fn test_entry() {
+		let right = hold(Direction::Right);
+		for (track, entry) in [
+			(0.0, HoldEntry::Direct),
+			(100.0, HoldEntry::Direct),
+			(120.0, HoldEntry::Teardrop),
+			(170.0, HoldEntry::Teardrop),
+			(190.0, HoldEntry::Parallel),
+			(280.0, HoldEntry::Parallel),
+			(300.0, HoldEntry::Direct),
+		] {
+			assert_eq!(right.entry(Angle::new::<degree>(track)), entry, "right {}", track);
+		}
+
+		let left = hold(Direction::Left);
+		for (track, entry) in [
+			(0.0, HoldEntry::Direct),
+			(60.0, HoldEntry::Direct),
+			(80.0, HoldEntry::Parallel),
+			(170.0, HoldEntry::Parallel),
+			(190.0, HoldEntry::Teardrop),
+			(240.0, HoldEntry::Teardrop),
+			(260.0, HoldEntry::Direct),
+		] {
+			assert_eq!(left.entry(Angle::new::<degree>(track)), entry, "left {}", track);
+		}
+	}
+
+	#[test]
+	fn test_racetrack() {
+		let hold = hold(Direction::Right);
+		let radius = Length::new::<nautical_mile>(1.5);
+		let ground_speed = Velocity::new::<knot>(180.0);
+
+		let racetrack = hold.racetrack(radius, ground_speed);
+
+		assert_uom_eq!(racetrack.inbound.length(), Length::new::<nautical_mile>(3.0), 1e-3);
+		assert_uom_eq!(racetrack.outbound.length(), Length::new::<nautical_mile>(3.0), 10.0);
+		assert_uom_eq!(racetrack.outbound_turn.length(), radius * PI, 1.0);
+		assert_uom_eq!(racetrack.inbound_turn.length(), radius * PI, 1.0);
+		assert_uom_eq!(racetrack.outbound_turn.sweep(), Angle::HALF_TURN, 1e-6);
+
+		// The holding side is east of the inbound course
+		assert!(racetrack.outbound.start.long > hold.fix.long);
+		assert_eq!(racetrack.outbound.end, racetrack.inbound_turn.start);
+		assert_eq!(racetrack.inbound_turn.end, racetrack.inbound.start);
+		assert_uom_eq!(racetrack.outbound.start_course(), Angle::new::<degree>(180.0), 1e-2);
+	}
+
+	#[test]
+	fn test_entry_path() {
+		let radius = Length::new::<nautical_mile>(1.5);
+		let ground_speed = Velocity::new::<knot>(180.0);
+
+		for direction in [Direction::Left, Direction::Right] {
+			let hold = hold(direction);
+
+			let (entry, path) = hold
+				.entry_path(Angle::new::<degree>(20.0), radius, ground_speed)
+				.unwrap();
+			assert_eq!(entry, HoldEntry::Direct);
+			assert_eq!(path[0].start(), hold.fix);
+			let outbound = hold.racetrack(radius, ground_speed).outbound;
+			assert_uom_eq!(
+				path[path.len() - 1]
+					.end()
+					.cross_track_distance(outbound.start, outbound.start_course()),
+				Length::new::<nautical_mile>(0.0),
+				1.0
+			);
+
+			for track in [150.0, 210.0] {
+				let (entry, path) = hold
+					.entry_path(Angle::new::<degree>(track), radius, ground_speed)
+					.unwrap();
+				assert_ne!(entry, HoldEntry::Direct);
+				assert_eq!(path.len(), 5);
+				assert_eq!(path[0].start(), hold.fix);
+				assert_eq!(path[path.len() - 1].end(), hold.fix);
+				for pair in path.windows(2) {
+					assert_eq!(pair[0].end(), pair[1].start());
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_wind_corrected_path_still_air() {
+		let hold = hold(Direction::Right);
+		let true_airspeed = Velocity::new::<knot>(200.0);
+
+		let path = hold
+			.wind_corrected_path(true_airspeed, Angle::new::<degree>(25.0), Wind::default())
+			.unwrap();
+
+		assert_uom_eq!(path.inbound_heading, Angle::ZERO, 1e-9);
+		assert_uom_eq!(path.outbound_heading, Angle::HALF_TURN, 1e-6);
+		assert_uom_eq!(path.outbound_time, Time::new::<minute>(1.0), 1e-3);
+		assert_eq!(path.ground_track[0], hold.fix);
+		assert_eq!(path.ground_track[path.ground_track.len() - 1], hold.fix);
+	}
+
+	#[test]
+	fn test_wind_corrected_path() {
+		let hold = hold(Direction::Right);
+		let true_airspeed = Velocity::new::<knot>(200.0);
+		let bank = Angle::new::<degree>(25.0);
+
+		// Crosswind from the left of the inbound course
+		let wind = Wind {
+			direction: Angle::new::<degree>(270.0),
+			speed: Velocity::new::<knot>(40.0),
+		};
+		let path = hold.wind_corrected_path(true_airspeed, bank, wind).unwrap();
+
+		let correction = diff_angle(hold.inbound_course, path.inbound_heading, Either);
+		assert!(correction < Angle::ZERO);
+		assert_uom_eq!(
+			correction,
+			Angle::new::<degree>(-11.537),
+			Angle::new::<degree>(1e-3).value
+		);
+
+		// Close to the rule of thumb of three times the inbound correction, which assumes standard rate turns
+		let outbound_correction = diff_angle(Angle::HALF_TURN, path.outbound_heading, Either);
+		assert_uom_eq!(
+			outbound_correction,
+			Angle::new::<degree>(36.069),
+			Angle::new::<degree>(1e-2).value
+		);
+		assert_uom_eq!(path.outbound_time, Time::new::<second>(72.73), 1e-2);
+
+		let inbound_start = path.ground_track[path.ground_track.len() - 2];
+		let (_, ground_speed) = wind.heading_for_track(hold.inbound_course, true_airspeed).unwrap();
+		assert_uom_eq!(
+			inbound_start.cross_track_distance(hold.fix, hold.inbound_course),
+			Length::ZERO,
+			1.0
+		);
+		assert_uom_eq!(
+			inbound_start.along_track_distance(hold.fix, hold.inbound_course),
+			-ground_speed * Time::new::<minute>(1.0),
+			1.0
+		);
+
+		// Headwind on the inbound leg
+		let wind = Wind {
+			direction: Angle::ZERO,
+			speed: Velocity::new::<knot>(40.0),
+		};
+		let path = hold.wind_corrected_path(true_airspeed, bank, wind).unwrap();
+		assert_uom_eq!(path.outbound_heading, Angle::HALF_TURN, 1e-6);
+		assert!(path.outbound_time < Time::new::<minute>(1.0));
+
+		let wind = Wind {
+			direction: Angle::ZERO,
+			speed: Velocity::new::<knot>(250.0),
+		};
+		assert!(hold.wind_corrected_path(true_airspeed, bank, wind).is_none());
+	}
+}
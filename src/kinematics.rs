@@ -0,0 +1,102 @@
+use uom::si::{
+	angular_velocity::radian_per_second,
+	f64::{Angle, AngularVelocity, Length, Ratio, Velocity},
+	ratio::ratio,
+};
+
+use crate::constants::{STANDARD_GRAVITY, STANDARD_RATE_TURN};
+
+/// Returns the radius of a level, coordinated turn at `ground_speed` and `bank` angle, in still air
+pub fn turn_radius(ground_speed: Velocity, bank: Angle) -> Length {
+	ground_speed * ground_speed / (STANDARD_GRAVITY * bank.tan())
+}
+
+/// Returns the rate of a level, coordinated turn at `ground_speed` and `bank` angle
+pub fn turn_rate(ground_speed: Velocity, bank: Angle) -> AngularVelocity {
+	AngularVelocity::new::<radian_per_second>((STANDARD_GRAVITY * bank.tan() / ground_speed).value)
+}
+
+/// Returns the bank angle of a level, coordinated turn at `radius` and `ground_speed`, in still air
+pub fn bank_for_radius(ground_speed: Velocity, radius: Length) -> Angle {
+	(ground_speed * ground_speed / (STANDARD_GRAVITY * radius)).atan()
+}
+
+/// Returns the bank angle of a level, coordinated turn at `rate` and `ground_speed`
+pub fn bank_for_rate(ground_speed: Velocity, rate: AngularVelocity) -> Angle {
+	let rate = rate.get::<radian_per_second>();
+
+	Ratio::new::<ratio>((ground_speed / STANDARD_GRAVITY).value * rate).atan()
+}
+
+/// Returns the bank angle for a standard rate turn at `ground_speed`, or `max_bank` if that is shallower
+pub fn standard_rate_bank(ground_speed: Velocity, max_bank: Angle) -> Angle {
+	bank_for_rate(ground_speed, STANDARD_RATE_TURN).min(max_bank)
+}
+
+/// Returns the largest radius of a turn at `true_airspeed` and `bank` angle, when a wind of `wind_speed` may blow from
+/// any direction, as used to protect the airspace around a turn
+pub fn worst_case_turn_radius(true_airspeed: Velocity, wind_speed: Velocity, bank: Angle) -> Length {
+	turn_radius(true_airspeed + wind_speed.abs(), bank)
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{
+		angle::degree,
+		angular_velocity::degree_per_second,
+		length::{meter, nautical_mile},
+		velocity::knot,
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_turn_radius() {
+		let radius = turn_radius(Velocity::new::<knot>(250.0), Angle::new::<degree>(25.0));
+		assert_uom_eq!(radius, Length::new::<nautical_mile>(1.953), 5.0);
+		assert_uom_eq!(
+			bank_for_radius(Velocity::new::<knot>(250.0), radius),
+			Angle::new::<degree>(25.0),
+			1e-12
+		);
+
+		let radius = worst_case_turn_radius(
+			Velocity::new::<knot>(220.0),
+			Velocity::new::<knot>(30.0),
+			Angle::new::<degree>(25.0),
+		);
+		assert_uom_eq!(radius, Length::new::<nautical_mile>(1.953), 5.0);
+	}
+
+	#[test]
+	fn test_turn_rate() {
+		let ground_speed = Velocity::new::<knot>(120.0);
+
+		let bank = bank_for_rate(ground_speed, STANDARD_RATE_TURN);
+		assert_uom_eq!(bank, Angle::new::<degree>(18.24), Angle::new::<degree>(0.01).value);
+		assert_uom_eq!(
+			turn_rate(ground_speed, bank),
+			AngularVelocity::new::<degree_per_second>(3.0),
+			1e-12
+		);
+
+		// A standard rate turn takes two minutes, so covers a circle whose circumference is two minutes of flight
+		let radius = turn_radius(ground_speed, bank);
+		assert_uom_eq!(
+			radius * 2.0 * std::f64::consts::PI,
+			Length::new::<meter>(ground_speed.value * 120.0),
+			1e-6
+		);
+
+		assert_eq!(
+			standard_rate_bank(Velocity::new::<knot>(400.0), Angle::new::<degree>(25.0)),
+			Angle::new::<degree>(25.0)
+		);
+		assert_uom_eq!(
+			standard_rate_bank(ground_speed, Angle::new::<degree>(25.0)),
+			bank,
+			1e-12
+		);
+	}
+}
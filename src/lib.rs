@@ -1,15 +1,37 @@
 extern crate core;
 
+pub mod arc_leg;
 mod bearing_distance;
 mod bearing_to;
+pub mod buffer;
 pub mod constants;
+pub mod coverage;
+pub mod critical_point;
+mod cross_track_distance;
+mod direct_to;
 mod distance_bounds;
 mod distance_to;
+pub mod guidance;
+pub mod holding;
+pub mod kinematics;
 pub mod macros;
+pub mod offset;
+pub mod path;
 mod place_bearing_intersection;
+pub mod polygon;
+pub mod procedure_turn;
+pub mod pseudo_waypoint;
+pub mod segment;
+pub mod sequencing;
+pub mod simplify;
 mod small_circle_great_circle_intersection;
+mod small_circle_polyline;
 mod spherical;
+pub mod termination;
+pub mod transition;
 pub mod utility;
+pub mod vertical_profile;
+pub mod wind;
 
 use uom::si::{angle::degree, f64::Angle};
 
@@ -0,0 +1,306 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{
+	path::Path,
+	segment::{ArcSegment, GreatCircleSegment, Segment},
+	Coordinates,
+	Direction,
+};
+
+/// Largest distance between a waypoint and the end of a segment for the waypoint to be treated as lying there
+const WAYPOINT_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+impl Path {
+	/// Returns the path flown parallel to `self`, `offset` to the right when positive or to the left when negative
+	/// (e.g. for a strategic lateral offset). The offset path leaves the start of `self` and rejoins it at the end of
+	/// the offset, intercepting the offset and the original path at `intercept_angle` (usually 30&deg; to 45&deg;).
+	///
+	/// Great circle segments are offset alongside the original ones, and turns between them are flown around the same
+	/// centre at a radius adjusted by the offset. Great circle segments which meet without a turn between them, as in a
+	/// path from `from_waypoints`, are joined where their offsets cross. The offset ends before the first segment which
+	/// cannot be offset: any arc which is not a turn of less than 180&deg; between two great circle segments (such as
+	/// a DME arc or the turns of a hold), and any turn on the side of the offset which is tighter than the offset
+	/// itself. The rest of the path is flown as before.
+	///
+	/// Returns `None` if the path does not start with a great circle segment which can be offset, or if the offset
+	/// portion is too short to fit the intercepts
+	pub fn parallel_offset(&self, offset: Length, intercept_angle: Angle) -> Option<Path> {
+		let segments = self.segments();
+		let span = offset_span(segments, offset);
+		let (first, last) = match (segments.first(), span.checked_sub(1).map(|index| segments[index])) {
+			(Some(Segment::GreatCircle(first)), Some(Segment::GreatCircle(last))) => (*first, last),
+			_ => return None,
+		};
+
+		let mut offset_segments: Vec<Segment> = segments[..span]
+			.iter()
+			.map(|segment| offset_segment(segment, offset))
+			.collect();
+		for index in 1..span {
+			if let (Segment::GreatCircle(before), Segment::GreatCircle(after)) =
+				(offset_segments[index - 1], offset_segments[index])
+			{
+				let join = corner_join(&before, &after, segments[index].start());
+				offset_segments[index - 1] = GreatCircleSegment::new(before.start, join).into();
+				offset_segments[index] = GreatCircleSegment::new(join, after.end).into();
+			}
+		}
+
+		let intercept_length = offset.abs() / intercept_angle.tan();
+		let (offset_first, offset_last) = match (offset_segments[0], offset_segments[span - 1]) {
+			(Segment::GreatCircle(first), Segment::GreatCircle(last)) => (first, last),
+			_ => return None,
+		};
+		let available = if span == 1 {
+			offset_first.length() / 2.0
+		} else {
+			offset_first.length().min(offset_last.length())
+		};
+		if intercept_length > available {
+			return None;
+		}
+
+		let entry = offset_first.point_at(intercept_length);
+		let exit = offset_last.point_at(offset_last.length() - intercept_length);
+		offset_segments[0] = GreatCircleSegment::new(entry, offset_segments[0].end()).into();
+		offset_segments[span - 1] = GreatCircleSegment::new(offset_segments[span - 1].start(), exit).into();
+
+		// Waypoints along the offset are moved abeam their original position
+		let waypoints = self.waypoint_segment_counts();
+		let mut path = Path::new();
+		if waypoints.contains(&0) {
+			path.push_waypoint();
+		}
+		path.push(GreatCircleSegment::new(first.start, entry));
+		for (index, segment) in offset_segments.into_iter().enumerate() {
+			path.push(segment);
+			if index < span - 1 && waypoints.contains(&(index + 1)) {
+				path.push_waypoint();
+			}
+		}
+		path.push(GreatCircleSegment::new(exit, last.end));
+		if waypoints.contains(&span) {
+			path.push_waypoint();
+		}
+		for (index, segment) in segments.iter().enumerate().skip(span) {
+			path.push(*segment);
+			if waypoints.contains(&(index + 1)) {
+				path.push_waypoint();
+			}
+		}
+
+		Some(path)
+	}
+
+	/// Returns the number of segments before each waypoint which lies at the start or end of a segment
+	fn waypoint_segment_counts(&self) -> Vec<usize> {
+		self.waypoint_distances()
+			.iter()
+			.filter_map(|distance| {
+				(0..=self.segments().len())
+					.rev()
+					.find(|count| (self.segment_start_distance(*count) - *distance).abs() < WAYPOINT_TOLERANCE)
+			})
+			.collect()
+	}
+}
+
+/// Returns the number of segments from the start of `segments` which can be offset by `offset`
+fn offset_span(segments: &[Segment], offset: Length) -> usize {
+	(0..segments.len())
+		.find(|index| match &segments[*index] {
+			Segment::GreatCircle(_) => false,
+			Segment::Arc(arc) => {
+				let is_turn = *index > 0
+					&& matches!(segments[index - 1], Segment::GreatCircle(_))
+					&& matches!(segments.get(index + 1), Some(Segment::GreatCircle(_)))
+					&& arc.sweep().abs() < Angle::HALF_TURN;
+
+				!is_turn || offset_radius(arc, offset) <= Length::ZERO
+			},
+		})
+		.unwrap_or(segments.len())
+}
+
+fn offset_segment(segment: &Segment, offset: Length) -> Segment {
+	match segment {
+		Segment::GreatCircle(segment) => offset_great_circle(segment, offset).into(),
+		Segment::Arc(arc) => {
+			let radius = offset_radius(arc, offset);
+			ArcSegment {
+				radius,
+				start: arc.centre.bearing_distance(arc.centre.bearing_to(arc.start), radius),
+				end: arc.centre.bearing_distance(arc.centre.bearing_to(arc.end), radius),
+				..*arc
+			}
+			.into()
+		},
+	}
+}
+
+fn offset_great_circle(segment: &GreatCircleSegment, offset: Length) -> GreatCircleSegment {
+	GreatCircleSegment::new(
+		segment
+			.start
+			.bearing_distance(segment.start_course() + Angle::HALF_TURN / 2.0, offset),
+		segment
+			.end
+			.bearing_distance(segment.end_course() + Angle::HALF_TURN / 2.0, offset),
+	)
+}
+
+/// Returns the point where the offset great circle segments `before` and `after` cross, near the `corner` of the
+/// original path where they meet. Segments which continue along the same great circle already meet, so are left as
+/// they are
+fn corner_join(before: &GreatCircleSegment, after: &GreatCircleSegment, corner: Coordinates) -> Coordinates {
+	let (first, second) =
+		before
+			.start
+			.place_bearing_intersection(before.start_course(), after.start, after.start_course());
+	if first.lat.value.is_nan() || first.long.value.is_nan() || before.end.distance_to(after.start) < WAYPOINT_TOLERANCE
+	{
+		return before.end;
+	}
+
+	if corner.distance_to(first) < corner.distance_to(second) {
+		first
+	} else {
+		second
+	}
+}
+
+/// Returns the radius of `arc` once offset, which shrinks when offset towards the inside of the turn
+fn offset_radius(arc: &ArcSegment, offset: Length) -> Length {
+	match arc.turn_direction() {
+		Direction::Left => arc.radius + offset,
+		_ => arc.radius - offset,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::{assert_uom_eq, path::turn_path};
+
+	fn assert_continuous(path: &Path) {
+		for pair in path.segments().windows(2) {
+			assert!(pair[0].end().distance_to(pair[1].start()) < Length::new::<nautical_mile>(1e-3));
+		}
+	}
+
+	#[test]
+	fn test_offset_with_turn() {
+		let (path, turn) = turn_path(Length::new::<nautical_mile>(3.0));
+		let (from, to) = (path.segments()[0].start(), path.segments()[2].end());
+
+		let offset = Length::new::<nautical_mile>(2.0);
+		let offset_path = path.parallel_offset(offset, Angle::new::<degree>(45.0)).unwrap();
+		let segments = offset_path.segments();
+		assert_continuous(&offset_path);
+		assert_eq!(segments.len(), 5);
+		assert_eq!(segments[0].start(), from);
+		assert_eq!(segments[4].end(), to);
+		assert_eq!(offset_path.waypoint_distances().len(), 2);
+		assert_uom_eq!(offset_path.waypoint_distances()[1], offset_path.length(), 1e-6);
+
+		// The entry intercepts the offset at 45 degrees
+		assert_uom_eq!(segments[0].length(), offset * 2.0f64.sqrt(), 1.0);
+		assert_uom_eq!(path.segments()[0].cross_track_distance(segments[1].end()), offset, 1.0);
+
+		// Offset to the outside of the left turn, so the radius grows
+		match segments[2] {
+			Segment::Arc(arc) => {
+				assert_uom_eq!(arc.radius, turn.arc.radius + offset, 1e-9);
+				assert_eq!(arc.centre, turn.arc.centre);
+			},
+			_ => panic!(),
+		}
+
+		// Offset further inside the turn than its radius, so the offset ends before it
+		let offset_path = path
+			.parallel_offset(Length::new::<nautical_mile>(-4.0), Angle::new::<degree>(45.0))
+			.unwrap();
+		assert_continuous(&offset_path);
+		assert_eq!(offset_path.segments().len(), 5);
+		assert_eq!(offset_path.segments()[3], Segment::Arc(turn.arc));
+	}
+
+	#[test]
+	fn test_offset_joins_corners() {
+		let corner = Coordinates::new(0.0, 1.0);
+		let path = Path::from_waypoints(&[Coordinates::new(0.0, 0.0), corner, Coordinates::new(1.0, 1.0)]);
+		let offset = Length::new::<nautical_mile>(2.0);
+
+		// Offset to the outside and the inside of the left turn at the corner
+		for offset in [offset, -offset] {
+			let offset_path = path.parallel_offset(offset, Angle::new::<degree>(45.0)).unwrap();
+			let segments = offset_path.segments();
+			assert_eq!(segments.len(), 4);
+			for pair in segments.windows(2) {
+				assert_eq!(pair[0].end(), pair[1].start());
+			}
+
+			let join = segments[1].end();
+			assert_uom_eq!(path.segments()[0].cross_track_distance(join), offset, 1.0);
+			assert_uom_eq!(path.segments()[1].cross_track_distance(join), offset, 1.0);
+			assert_uom_eq!(corner.distance_to(join), offset.abs() * 2.0f64.sqrt(), 1.0);
+			assert_uom_eq!(
+				offset_path.waypoint_distances()[1],
+				offset_path.segment_start_distance(2),
+				1e-6
+			);
+		}
+	}
+
+	#[test]
+	fn test_offset_stops_before_arc() {
+		let navaid = Coordinates::new(40.0, -75.0);
+		let distance = Length::new::<nautical_mile>(10.0);
+		let arc = ArcSegment {
+			centre: navaid,
+			radius: distance,
+			start: navaid.bearing_distance(Angle::new::<degree>(270.0), distance),
+			end: navaid.bearing_distance(Angle::ZERO, distance),
+			direction: Direction::Right,
+		};
+		let start = arc
+			.start
+			.bearing_distance(Angle::new::<degree>(180.0), Length::new::<nautical_mile>(20.0));
+
+		let mut path = Path::from_waypoints(&[start]);
+		path.push(GreatCircleSegment::new(start, arc.start));
+		path.push_waypoint();
+		path.push(arc);
+		path.push_waypoint();
+
+		let offset_path = path
+			.parallel_offset(Length::new::<nautical_mile>(2.0), Angle::new::<degree>(30.0))
+			.unwrap();
+		let segments = offset_path.segments();
+		assert_continuous(&offset_path);
+		assert_eq!(segments.len(), 4);
+		assert_eq!(segments[2].end(), arc.start);
+		assert_eq!(segments[3], Segment::Arc(arc));
+		assert_eq!(offset_path.waypoint_distances().len(), 3);
+		assert_uom_eq!(
+			offset_path.waypoint_distances()[1],
+			offset_path.segment_start_distance(3),
+			1e-6
+		);
+
+		assert!(path
+			.parallel_offset(Length::new::<nautical_mile>(20.0), Angle::new::<degree>(30.0))
+			.is_none());
+	}
+}
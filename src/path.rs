@@ -0,0 +1,247 @@
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{
+	segment::{GreatCircleSegment, Segment},
+	transition::FlyByTurn,
+	Coordinates,
+};
+
+/// An ordered sequence of segments, each starting where the previous one ends, with waypoints marked at some of the
+/// joins between them and abeam the middle of fly-by turns
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+	segments: Vec<Segment>,
+	/// Distance along the path to the end of each segment
+	ends: Vec<Length>,
+	/// Distance along the path to each waypoint
+	waypoints: Vec<Length>,
+}
+
+impl Path {
+	pub fn new() -> Self { Self::default() }
+
+	/// Returns the great circle path through `waypoints`, with each of them marked as a waypoint
+	pub fn from_waypoints(waypoints: &[Coordinates]) -> Self {
+		let mut path = Path::new();
+		if !waypoints.is_empty() {
+			path.push_waypoint();
+		}
+		for leg in waypoints.windows(2) {
+			path.push(GreatCircleSegment::new(leg[0], leg[1]));
+			path.push_waypoint();
+		}
+
+		path
+	}
+
+	/// Appends `segment` to the end of the path
+	pub fn push(&mut self, segment: impl Into<Segment>) {
+		let segment = segment.into();
+		self.ends.push(self.length() + segment.length());
+		self.segments.push(segment);
+	}
+
+	/// Marks the current end of the path as a waypoint
+	pub fn push_waypoint(&mut self) { self.waypoints.push(self.length()); }
+
+	/// Appends the arc of the fly-by `turn` to the end of the path, marking its waypoint abeam the middle of the turn,
+	/// where the turn crosses its bisector
+	pub fn push_fly_by_turn(&mut self, turn: &FlyByTurn) {
+		self.push(turn.arc);
+		self.waypoints.push(self.length() - turn.arc.length() / 2.0);
+	}
+
+	pub fn segments(&self) -> &[Segment] { &self.segments }
+
+	/// Returns the distance along the path to each waypoint
+	pub fn waypoint_distances(&self) -> &[Length] { &self.waypoints }
+
+	pub fn length(&self) -> Length { self.ends.last().copied().unwrap_or(Length::ZERO) }
+
+	/// Returns the distance along the path to the start of the segment at `index`
+	pub fn segment_start_distance(&self, index: usize) -> Length {
+		if index == 0 {
+			Length::ZERO
+		} else {
+			self.ends[index - 1]
+		}
+	}
+
+	/// Returns the index of the segment `distance` along the path. Distances before the start or beyond the end of the
+	/// path give the first or last segment. Returns `None` if the path is empty
+	pub fn segment_at(&self, distance: Length) -> Option<usize> {
+		if self.segments.is_empty() {
+			return None;
+		}
+
+		Some(
+			self.ends
+				.partition_point(|end| *end <= distance)
+				.min(self.segments.len() - 1),
+		)
+	}
+
+	/// Returns the point `distance` along the path, clamped to the ends of the path. Returns `None` if the path is
+	/// empty
+	pub fn point_at(&self, distance: Length) -> Option<Coordinates> {
+		let distance = distance.max(Length::ZERO).min(self.length());
+		let index = self.segment_at(distance)?;
+
+		Some(self.segments[index].point_at(distance - self.segment_start_distance(index)))
+	}
+
+	/// Returns the course of the path `distance` along it, clamped to the ends of the path. Returns `None` if the path
+	/// is empty
+	pub fn course_at(&self, distance: Length) -> Option<Angle> {
+		let distance = distance.max(Length::ZERO).min(self.length());
+		let index = self.segment_at(distance)?;
+		let segment = &self.segments[index];
+
+		Some(segment.course_at(segment.point_at(distance - self.segment_start_distance(index))))
+	}
+
+	/// Returns the index of the segment closest to `position`. Returns `None` if the path is empty
+	pub fn nearest_segment(&self, position: Coordinates) -> Option<usize> {
+		self.segments
+			.iter()
+			.map(|segment| {
+				let along = segment.along_track_distance(position);
+				if along < Length::ZERO {
+					position.distance_to(segment.start())
+				} else if along > segment.length() {
+					position.distance_to(segment.end())
+				} else {
+					segment.cross_track_distance(position).abs()
+				}
+			})
+			.enumerate()
+			.min_by(|(_, a), (_, b)| a.value.total_cmp(&b.value))
+			.map(|(index, _)| index)
+	}
+
+	/// Returns the distance along the path to the point abeam `position` on the closest segment. Positions before the
+	/// start or beyond the end of the path give distances before or beyond its ends. Returns `None` if the path is
+	/// empty
+	pub fn distance_along(&self, position: Coordinates) -> Option<Length> {
+		let index = self.nearest_segment(position)?;
+		let segment = &self.segments[index];
+
+		let mut along = segment.along_track_distance(position);
+		if index > 0 {
+			along = along.max(Length::ZERO);
+		}
+		if index < self.segments.len() - 1 {
+			along = along.min(segment.length());
+		}
+
+		Some(self.segment_start_distance(index) + along)
+	}
+
+	/// Returns the distance remaining along the path from `position` to its end. Returns `None` if the path is empty
+	pub fn distance_to_go(&self, position: Coordinates) -> Option<Length> {
+		Some(self.length() - self.distance_along(position)?)
+	}
+
+	/// Returns the distance remaining along the path from `position` to each waypoint, which is negative for waypoints
+	/// already passed
+	pub fn distances_to_waypoints(&self, position: Coordinates) -> Vec<Length> {
+		match self.distance_along(position) {
+			Some(along) => self.waypoints.iter().map(|waypoint| *waypoint - along).collect(),
+			None => Vec::new(),
+		}
+	}
+}
+
+/// Returns the path east from (0, 0) to a fly-by waypoint at (0, 1) and north to (1, 1), turning left at `radius`
+/// between the two legs, along with the turn
+#[cfg(test)]
+pub(crate) fn turn_path(radius: Length) -> (Path, FlyByTurn) {
+	use uom::si::angle::degree;
+
+	let from = Coordinates::new(0.0, 0.0);
+	let waypoint = Coordinates::new(0.0, 1.0);
+	let to = Coordinates::new(1.0, 1.0);
+	let turn = waypoint
+		.fly_by_turn(
+			Angle::new::<degree>(90.0),
+			Angle::ZERO,
+			radius,
+			crate::Direction::Either,
+		)
+		.unwrap();
+
+	let mut path = Path::from_waypoints(&[from]);
+	path.push(GreatCircleSegment::new(from, turn.arc.start));
+	path.push(turn.arc);
+	path.push(GreatCircleSegment::new(turn.arc.end, to));
+	path.push_waypoint();
+
+	(path, turn)
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_great_circle_path() {
+		let path = Path::from_waypoints(&[
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.0, 1.0),
+			Coordinates::new(1.0, 1.0),
+		]);
+		let leg = Coordinates::new(0.0, 0.0).distance_to(Coordinates::new(0.0, 1.0));
+
+		assert_eq!(path.segments().len(), 2);
+		assert_uom_eq!(path.length(), 2.0 * leg, 1e-3);
+		assert_eq!(path.waypoint_distances().len(), 3);
+
+		let point = path.point_at(1.5 * leg).unwrap();
+		assert_uom_eq!(point.lat, Angle::new::<degree>(0.5), 1e-9);
+		assert_uom_eq!(point.long, Angle::new::<degree>(1.0), 1e-9);
+		assert_uom_eq!(path.course_at(0.5 * leg).unwrap(), Angle::new::<degree>(90.0), 1e-3);
+		assert_uom_eq!(path.course_at(1.5 * leg).unwrap(), Angle::ZERO, 1e-9);
+
+		let position = Coordinates::new(0.01, 0.5);
+		assert_uom_eq!(path.distance_along(position).unwrap(), 0.5 * leg, 1.0);
+		assert_uom_eq!(path.distance_to_go(position).unwrap(), 1.5 * leg, 1.0);
+
+		let distances = path.distances_to_waypoints(position);
+		assert_uom_eq!(distances[0], -0.5 * leg, 1.0);
+		assert_uom_eq!(distances[1], 0.5 * leg, 1.0);
+		assert_uom_eq!(distances[2], 1.5 * leg, 1.0);
+
+		assert!(Path::new().point_at(Length::ZERO).is_none());
+	}
+
+	#[test]
+	fn test_path_with_turn() {
+		let (path, turn) = turn_path(Length::new::<nautical_mile>(5.0));
+
+		let turn_start = path.segment_start_distance(1);
+		assert_uom_eq!(
+			path.point_at(turn_start + turn.arc.length()).unwrap().lat,
+			turn.arc.end.lat,
+			1e-9
+		);
+
+		let halfway = turn_start + turn.arc.length() / 2.0;
+		assert_eq!(path.segment_at(halfway), Some(1));
+		assert_uom_eq!(path.course_at(halfway).unwrap(), Angle::new::<degree>(45.0), 1e-3);
+
+		// Inside the turn, abeam its midpoint
+		let position = turn
+			.arc
+			.centre
+			.bearing_distance(Angle::new::<degree>(135.0), Length::new::<nautical_mile>(4.0));
+		assert_eq!(path.nearest_segment(position), Some(1));
+		assert_uom_eq!(path.distance_along(position).unwrap(), halfway, 1.0);
+		assert_uom_eq!(path.distances_to_waypoints(position)[1], path.length() - halfway, 1.0);
+	}
+}
@@ -0,0 +1,455 @@
+use std::f64::consts::PI;
+
+use uom::si::f64::Area;
+
+use crate::{constants::EARTH_RADIUS, spherical::Spherical, Coordinates};
+
+/// Tolerance, in radians of arc, within which two points on the unit sphere are treated as the same point
+const EPSILON: f64 = 1e-10;
+
+/// A polygon on the surface of the sphere, bounded by great circle edges
+///
+/// Each ring bounds the smaller of the two regions it divides the sphere into, so polygons must fit within a
+/// hemisphere. Rings are stored without a closing point, with the exterior anticlockwise and the interiors (holes)
+/// clockwise, so that the inside of the polygon is always on the left of an edge.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polygon {
+	exterior: Vec<Coordinates>,
+	interiors: Vec<Vec<Coordinates>>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Operation {
+	Union,
+	Intersection,
+	Difference,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum EdgeClass {
+	Inside,
+	Outside,
+	SharedSame,
+	SharedOpposite,
+}
+
+impl Polygon {
+	/// Instantiates a Polygon from an `exterior` ring and any number of `interiors` (holes). The rings may be given in
+	/// either winding order, and may optionally repeat their first point at the end
+	pub fn new(exterior: Vec<Coordinates>, interiors: Vec<Vec<Coordinates>>) -> Self {
+		Self {
+			exterior: orient_ring(exterior, true),
+			interiors: interiors.into_iter().map(|ring| orient_ring(ring, false)).collect(),
+		}
+	}
+
+	/// The outer boundary of the polygon, anticlockwise
+	pub fn exterior(&self) -> &[Coordinates] { &self.exterior }
+
+	/// The holes in the polygon, each clockwise
+	pub fn interiors(&self) -> &[Vec<Coordinates>] { &self.interiors }
+
+	/// Returns the area enclosed by the polygon, excluding its holes
+	pub fn area(&self) -> Area {
+		let exterior = ring_area(&to_ring(&self.exterior));
+		let interiors: f64 = self.interiors.iter().map(|ring| ring_area(&to_ring(ring))).sum();
+
+		EARTH_RADIUS * EARTH_RADIUS * (exterior - interiors)
+	}
+
+	/// Returns whether `point` lies inside the polygon. Points inside a hole are not contained
+	pub fn contains(&self, point: Coordinates) -> bool { winding_number(&self.rings(), point.into()) > 0 }
+
+	/// Returns the polygons covering the area inside either `self` or `other`
+	pub fn union(&self, other: &Polygon) -> Vec<Polygon> { overlay(self, other, Operation::Union) }
+
+	/// Returns the polygons covering the area inside both `self` and `other`
+	pub fn intersection(&self, other: &Polygon) -> Vec<Polygon> { overlay(self, other, Operation::Intersection) }
+
+	/// Returns the polygons covering the area inside `self` but outside `other`
+	pub fn difference(&self, other: &Polygon) -> Vec<Polygon> { overlay(self, other, Operation::Difference) }
+
+	fn rings(&self) -> Vec<Vec<Spherical>> {
+		std::iter::once(&self.exterior)
+			.chain(self.interiors.iter())
+			.map(|ring| to_ring(ring))
+			.filter(|ring| ring.len() >= 3)
+			.collect()
+	}
+}
+
+fn to_ring(points: &[Coordinates]) -> Vec<Spherical> {
+	let mut ring: Vec<Spherical> = Vec::with_capacity(points.len());
+	for &point in points {
+		let point: Spherical = point.into();
+		if ring.last().is_none_or(|&last| !same_point(last, point)) {
+			ring.push(point);
+		}
+	}
+
+	while ring.len() > 1 && same_point(ring[0], ring[ring.len() - 1]) {
+		ring.pop();
+	}
+
+	ring
+}
+
+fn orient_ring(points: Vec<Coordinates>, anticlockwise: bool) -> Vec<Coordinates> {
+	let ring = to_ring(&points);
+	let mut points: Vec<Coordinates> = ring.iter().map(|&point| Coordinates::from(point)).collect();
+
+	if ring.len() >= 3 && (ring_turn(&ring) > 0.0) != anticlockwise {
+		points.reverse();
+	}
+
+	points
+}
+
+fn same_point(a: Spherical, b: Spherical) -> bool { (a - b).norm() < EPSILON }
+
+/// Returns the signed angle, positive to the left, between the direction of travel along the great circle from `a`
+/// to `b` and the direction of travel along the great circle from `b` to `c`, measured at `b`
+fn turn_angle(a: Spherical, b: Spherical, c: Spherical) -> f64 {
+	let incoming = a.cross(&b).cross(&b);
+	let outgoing = b.cross(&c).cross(&b);
+
+	incoming.cross(&outgoing).dot(&b).atan2(incoming.dot(&outgoing))
+}
+
+/// Returns the total turn made travelling once around `ring`, positive when its left hand side is the smaller region
+fn ring_turn(ring: &[Spherical]) -> f64 {
+	let n = ring.len();
+	(0..n)
+		.map(|i| turn_angle(ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]))
+		.sum()
+}
+
+/// Returns the unsigned area of the smaller region bounded by `ring`, on the unit sphere
+fn ring_area(ring: &[Spherical]) -> f64 {
+	if ring.len() < 3 {
+		return 0.0;
+	}
+
+	2.0 * PI - ring_turn(ring).abs()
+}
+
+/// Returns the number of times `rings` wind anticlockwise around `point`
+fn winding_number(rings: &[Vec<Spherical>], point: Spherical) -> i32 {
+	let mut total = 0.0;
+	for ring in rings {
+		let n = ring.len();
+		for i in 0..n {
+			let a = ring[i] - point * ring[i].dot(&point);
+			let b = ring[(i + 1) % n] - point * ring[(i + 1) % n].dot(&point);
+			total += a.cross(&b).dot(&point).atan2(a.dot(&b));
+		}
+	}
+
+	(total / (2.0 * PI)).round() as i32
+}
+
+fn edges(rings: &[Vec<Spherical>]) -> Vec<(Spherical, Spherical)> {
+	rings
+		.iter()
+		.flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+		.collect()
+}
+
+/// Returns whether `point` lies on the great circle arc from `start` to `end`, which has the unit normal `normal`
+fn on_arc(start: Spherical, end: Spherical, normal: Spherical, point: Spherical) -> bool {
+	point.dot(&normal).abs() < EPSILON
+		&& start.cross(&point).dot(&normal) >= -EPSILON
+		&& point.cross(&end).dot(&normal) >= -EPSILON
+		&& point.dot(&(start + end)) > 0.0
+}
+
+/// Returns the points where the great circle arcs `a` and `b` cross or overlap. Points close to an end of either arc
+/// are snapped onto that end, so that both arcs are split at exactly the same place
+fn edge_intersections(a: (Spherical, Spherical), b: (Spherical, Spherical)) -> Vec<Spherical> {
+	let normal_a = a.0.cross(&a.1).normalize();
+	let normal_b = b.0.cross(&b.1).normalize();
+
+	let snap = |point: Spherical| {
+		[a.0, a.1, b.0, b.1]
+			.into_iter()
+			.find(|&end| same_point(end, point))
+			.unwrap_or(point)
+	};
+
+	let line = normal_a.cross(&normal_b);
+	if line.norm() < EPSILON {
+		// Both arcs lie on the same great circle, so any overlap is bounded by their ends
+		let mut points: Vec<Spherical> = Vec::new();
+		for (end, start, finish, normal) in [
+			(b.0, a.0, a.1, normal_a),
+			(b.1, a.0, a.1, normal_a),
+			(a.0, b.0, b.1, normal_b),
+			(a.1, b.0, b.1, normal_b),
+		] {
+			if on_arc(start, finish, normal, end) && !points.iter().any(|&point| same_point(point, end)) {
+				points.push(end);
+			}
+		}
+		return points;
+	}
+
+	let line = line.normalize();
+	[line, -line]
+		.into_iter()
+		.map(snap)
+		.filter(|&point| on_arc(a.0, a.1, normal_a, point) && on_arc(b.0, b.1, normal_b, point))
+		.take(1)
+		.collect()
+}
+
+/// Splits each edge at the given points, returning the pieces in order
+fn split_edges(edges: &[(Spherical, Spherical)], splits: Vec<Vec<Spherical>>) -> Vec<(Spherical, Spherical)> {
+	let mut pieces = Vec::new();
+	for (&(start, end), mut points) in edges.iter().zip(splits) {
+		let distance = |point: &Spherical| start.cross(point).norm().atan2(start.dot(point));
+		points.retain(|&point| !same_point(point, start) && !same_point(point, end));
+		points.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+		points.dedup_by(|a, b| same_point(*a, *b));
+
+		let mut previous = start;
+		for point in points.into_iter().chain(std::iter::once(end)) {
+			pieces.push((previous, point));
+			previous = point;
+		}
+	}
+
+	pieces
+}
+
+/// Classifies an `edge` of one polygon against the `rings` and `edges` of the other
+fn classify(edge: (Spherical, Spherical), rings: &[Vec<Spherical>], edges: &[(Spherical, Spherical)]) -> EdgeClass {
+	let midpoint = (edge.0 + edge.1).normalize();
+	let normal = edge.0.cross(&edge.1);
+
+	for &(start, end) in edges {
+		let other_normal = start.cross(&end).normalize();
+		if on_arc(start, end, other_normal, midpoint) {
+			return if normal.dot(&other_normal) > 0.0 {
+				EdgeClass::SharedSame
+			} else {
+				EdgeClass::SharedOpposite
+			};
+		}
+	}
+
+	if winding_number(rings, midpoint) > 0 {
+		EdgeClass::Inside
+	} else {
+		EdgeClass::Outside
+	}
+}
+
+/// Joins directed edges end to start into closed rings
+fn chain(mut edges: Vec<(Spherical, Spherical)>) -> Vec<Vec<Spherical>> {
+	let mut rings = Vec::new();
+	while let Some((start, mut current)) = edges.pop() {
+		let mut ring = vec![start];
+		while !same_point(current, start) {
+			match edges.iter().position(|&(from, _)| same_point(from, current)) {
+				Some(index) => {
+					ring.push(current);
+					current = edges.swap_remove(index).1;
+				},
+				None => break,
+			}
+		}
+
+		if same_point(current, start) && ring.len() >= 3 {
+			rings.push(ring);
+		}
+	}
+
+	rings
+}
+
+fn overlay(a: &Polygon, b: &Polygon, operation: Operation) -> Vec<Polygon> {
+	let rings_a = a.rings();
+	let rings_b = b.rings();
+	let edges_a = edges(&rings_a);
+	let edges_b = edges(&rings_b);
+
+	let mut splits_a = vec![Vec::new(); edges_a.len()];
+	let mut splits_b = vec![Vec::new(); edges_b.len()];
+	for (i, &edge_a) in edges_a.iter().enumerate() {
+		for (j, &edge_b) in edges_b.iter().enumerate() {
+			for point in edge_intersections(edge_a, edge_b) {
+				splits_a[i].push(point);
+				splits_b[j].push(point);
+			}
+		}
+	}
+
+	let mut selected = Vec::new();
+	for edge in split_edges(&edges_a, splits_a) {
+		let keep = matches!(
+			(operation, classify(edge, &rings_b, &edges_b)),
+			(Operation::Union, EdgeClass::Outside | EdgeClass::SharedSame)
+				| (Operation::Intersection, EdgeClass::Inside | EdgeClass::SharedSame)
+				| (Operation::Difference, EdgeClass::Outside | EdgeClass::SharedOpposite)
+		);
+		if keep {
+			selected.push(edge);
+		}
+	}
+	for edge in split_edges(&edges_b, splits_b) {
+		match (operation, classify(edge, &rings_a, &edges_a)) {
+			(Operation::Union, EdgeClass::Outside) | (Operation::Intersection, EdgeClass::Inside) => {
+				selected.push(edge)
+			},
+			(Operation::Difference, EdgeClass::Inside) => selected.push((edge.1, edge.0)),
+			_ => (),
+		}
+	}
+
+	assemble(chain(selected))
+}
+
+/// Groups rings into polygons, assigning each clockwise ring to the smallest anticlockwise ring containing it
+fn assemble(rings: Vec<Vec<Spherical>>) -> Vec<Polygon> {
+	let (exteriors, interiors): (Vec<_>, Vec<_>) = rings.into_iter().partition(|ring| ring_turn(ring) > 0.0);
+
+	let to_coordinates = |ring: &[Spherical]| ring.iter().map(|&point| Coordinates::from(point)).collect::<Vec<_>>();
+
+	let mut polygons: Vec<Polygon> = exteriors
+		.iter()
+		.map(|ring| Polygon {
+			exterior: to_coordinates(ring),
+			interiors: Vec::new(),
+		})
+		.collect();
+
+	for interior in interiors {
+		let owner = exteriors
+			.iter()
+			.enumerate()
+			.filter(|(_, exterior)| winding_number(&[exterior.to_vec()], interior[0]) > 0)
+			.min_by(|(_, a), (_, b)| ring_area(a).total_cmp(&ring_area(b)))
+			.map(|(index, _)| index);
+
+		if let Some(index) = owner {
+			polygons[index].interiors.push(to_coordinates(&interior));
+		}
+	}
+
+	polygons
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::f64::Area;
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	fn square(lat: f64, long: f64, size: f64) -> Vec<Coordinates> {
+		vec![
+			Coordinates::new(lat, long),
+			Coordinates::new(lat, long + size),
+			Coordinates::new(lat + size, long + size),
+			Coordinates::new(lat + size, long),
+		]
+	}
+
+	fn total_area(polygons: &[Polygon]) -> Area { polygons.iter().map(|polygon| polygon.area()).sum() }
+
+	#[test]
+	fn test_contains() {
+		let polygon = Polygon::new(square(0.0, 0.0, 4.0), vec![square(1.0, 1.0, 2.0)]);
+
+		assert!(polygon.contains(Coordinates::new(0.5, 0.5)));
+		assert!(!polygon.contains(Coordinates::new(2.0, 2.0)));
+		assert!(!polygon.contains(Coordinates::new(5.0, 2.0)));
+
+		let polygon = Polygon::new(square(10.0, 178.0, 4.0), vec![]);
+		assert!(polygon.contains(Coordinates::new(12.0, -179.0)));
+		assert!(polygon.contains(Coordinates::new(12.0, 179.0)));
+		assert!(!polygon.contains(Coordinates::new(12.0, -170.0)));
+	}
+
+	#[test]
+	fn test_orientation() {
+		let mut clockwise = square(0.0, 0.0, 2.0);
+		clockwise.reverse();
+		let polygon = Polygon::new(clockwise, vec![square(0.5, 0.5, 1.0)]);
+
+		assert!(ring_turn(&to_ring(polygon.exterior())) > 0.0);
+		assert!(ring_turn(&to_ring(&polygon.interiors()[0])) < 0.0);
+		assert!(polygon.contains(Coordinates::new(0.25, 0.25)));
+	}
+
+	#[test]
+	fn test_overlapping() {
+		let a = Polygon::new(square(0.0, 0.0, 2.0), vec![]);
+		let b = Polygon::new(square(1.0, 1.0, 2.0), vec![]);
+
+		let union = a.union(&b);
+		let intersection = a.intersection(&b);
+		let difference = a.difference(&b);
+
+		assert_eq!(union.len(), 1);
+		assert_eq!(union[0].exterior().len(), 8);
+		assert_eq!(intersection.len(), 1);
+		assert_eq!(intersection[0].exterior().len(), 4);
+		assert_eq!(difference.len(), 1);
+		assert_eq!(difference[0].exterior().len(), 6);
+
+		assert_uom_eq!(total_area(&union) + total_area(&intersection), a.area() + b.area(), 1e3);
+		assert_uom_eq!(total_area(&difference) + total_area(&intersection), a.area(), 1e3);
+		assert!(intersection[0].contains(Coordinates::new(1.5, 1.5)));
+		assert!(!difference[0].contains(Coordinates::new(1.5, 1.5)));
+	}
+
+	#[test]
+	fn test_shared_edge() {
+		let a = Polygon::new(square(0.0, 0.0, 1.0), vec![]);
+		let b = Polygon::new(square(0.0, 1.0, 1.0), vec![]);
+
+		let union = a.union(&b);
+		assert_eq!(union.len(), 1);
+		assert_uom_eq!(union[0].area(), a.area() + b.area(), 1e3);
+		assert!(a.intersection(&b).is_empty());
+
+		let difference = a.difference(&b);
+		assert_eq!(difference.len(), 1);
+		assert_uom_eq!(difference[0].area(), a.area(), 1e3);
+	}
+
+	#[test]
+	fn test_hole() {
+		let outer = Polygon::new(square(0.0, 0.0, 4.0), vec![]);
+		let inner = Polygon::new(square(1.0, 1.0, 2.0), vec![]);
+
+		let difference = outer.difference(&inner);
+		assert_eq!(difference.len(), 1);
+		assert_eq!(difference[0].interiors().len(), 1);
+		assert!(!difference[0].contains(Coordinates::new(2.0, 2.0)));
+		assert_uom_eq!(difference[0].area(), outer.area() - inner.area(), 1e3);
+
+		let union = difference[0].union(&inner);
+		assert_eq!(union.len(), 1);
+		assert!(union[0].interiors().is_empty());
+		assert_uom_eq!(union[0].area(), outer.area(), 1e3);
+	}
+
+	#[test]
+	fn test_antimeridian() {
+		let a = Polygon::new(square(-1.0, 178.0, 4.0), vec![]);
+		let b = Polygon::new(square(0.0, -179.0, 2.0), vec![]);
+
+		let intersection = a.intersection(&b);
+		assert_eq!(intersection.len(), 1);
+		assert!(intersection[0].contains(Coordinates::new(1.0, -178.5)));
+		assert!(!intersection[0].contains(Coordinates::new(1.0, -177.5)));
+
+		let union = a.union(&b);
+		assert_eq!(union.len(), 1);
+		assert!(union[0].contains(Coordinates::new(1.0, 179.0)));
+		assert!(union[0].contains(Coordinates::new(1.0, -177.5)));
+		assert_uom_eq!(total_area(&union) + total_area(&intersection), a.area() + b.area(), 1e3);
+	}
+}
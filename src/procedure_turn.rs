@@ -0,0 +1,356 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{
+	segment::{turn_direction, ArcSegment, GreatCircleSegment, Segment},
+	utility::diff_angle,
+	Coordinates,
+	Direction,
+};
+
+/// Angle between the outbound course and the offset leg of a procedure turn, which is also the angle at which the
+/// inbound course is intercepted
+const PROCEDURE_TURN_ANGLE: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: std::f64::consts::FRAC_PI_4,
+};
+
+/// Angle at which a base turn intercepts the inbound course when it rolls out short of it
+const BASE_TURN_INTERCEPT_ANGLE: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: std::f64::consts::FRAC_PI_4,
+};
+
+/// Largest distance the points used to find the furthest extent of a procedure turn may stray from the true arc
+const MAX_CHORD_ERROR: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1.0,
+};
+
+/// The ground path of a 45&deg;/180&deg; procedure turn (ARINC 424 PI leg), starting and ending at the fix
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProcedureTurn {
+	/// Outbound from the fix on the reciprocal of the inbound course
+	pub outbound: GreatCircleSegment,
+	/// The 45&deg; turn off the outbound course
+	pub outbound_turn: ArcSegment,
+	/// The leg flown 45&deg; off the outbound course
+	pub offset_leg: GreatCircleSegment,
+	/// The 180&deg; turn back towards the inbound course
+	pub reversal_turn: ArcSegment,
+	/// The 45&deg; intercept of the inbound course
+	pub intercept: GreatCircleSegment,
+	/// Inbound to the fix
+	pub inbound: GreatCircleSegment,
+}
+
+/// The ground path of a base turn, starting and ending at the fix
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BaseTurn {
+	/// Outbound from the fix, offset from the reciprocal of the inbound course
+	pub outbound: GreatCircleSegment,
+	/// The turn back towards the inbound course
+	pub turn: ArcSegment,
+	/// The intercept of the inbound course, which has no length if the turn rolls out on the inbound course
+	pub intercept: GreatCircleSegment,
+	/// Inbound to the fix
+	pub inbound: GreatCircleSegment,
+}
+
+impl ProcedureTurn {
+	pub fn segments(&self) -> Vec<Segment> {
+		vec![
+			self.outbound.into(),
+			self.outbound_turn.into(),
+			self.offset_leg.into(),
+			self.reversal_turn.into(),
+			self.intercept.into(),
+			self.inbound.into(),
+		]
+	}
+}
+
+impl BaseTurn {
+	pub fn segments(&self) -> Vec<Segment> {
+		vec![
+			self.outbound.into(),
+			self.turn.into(),
+			self.intercept.into(),
+			self.inbound.into(),
+		]
+	}
+}
+
+impl Coordinates {
+	/// Returns the procedure turn at fix `self` for `inbound_course`, turning 45&deg; off the outbound course in
+	/// `direction` (`Left` or `Right`) and flying `offset_leg_length` before the 180&deg; turn the other way. Turns
+	/// are flown at `radius`.
+	///
+	/// The outbound leg is as long as possible while keeping the whole turn within `limit_distance` of the fix.
+	/// Returns `None` if the turn cannot fit within that distance, or if the 180&deg; turn would roll out beyond the
+	/// inbound course, as then a longer offset leg is needed to intercept it
+	pub fn procedure_turn(
+		self, inbound_course: Angle, direction: Direction, limit_distance: Length, offset_leg_length: Length,
+		radius: Length,
+	) -> Option<ProcedureTurn> {
+		let direction = turn_direction(direction);
+		let reversal_direction = match direction {
+			Direction::Left => Direction::Right,
+			_ => Direction::Left,
+		};
+		let outbound_course = inbound_course + Angle::HALF_TURN;
+		let offset_course = match direction {
+			Direction::Left => outbound_course - PROCEDURE_TURN_ANGLE,
+			_ => outbound_course + PROCEDURE_TURN_ANGLE,
+		};
+
+		let build = |outbound_length: Length| -> Option<ProcedureTurn> {
+			let outbound = GreatCircleSegment::new(self, self.bearing_distance(outbound_course, outbound_length));
+			let course = if outbound_length > Length::ZERO {
+				outbound.end_course()
+			} else {
+				outbound_course
+			};
+			let outbound_turn = outbound.end.turn_to_course(course, offset_course, radius, direction);
+			let offset_course = outbound_turn.end_course();
+			let offset_leg = GreatCircleSegment::new(
+				outbound_turn.end,
+				outbound_turn.end.bearing_distance(offset_course, offset_leg_length),
+			);
+			let reversal_turn = offset_leg.end.turn_to_course(
+				offset_course,
+				offset_course + Angle::HALF_TURN,
+				radius,
+				reversal_direction,
+			);
+
+			// Rolling out beyond the inbound course leaves only the intersection on the far side of the planet ahead
+			let offset_side = offset_leg.end.cross_track_distance(self, inbound_course);
+			let rollout_side = reversal_turn.end.cross_track_distance(self, inbound_course);
+			if (offset_side < Length::ZERO) != (rollout_side < Length::ZERO) {
+				return None;
+			}
+
+			let heading = reversal_turn.end_course();
+			let join = reversal_turn
+				.end
+				.place_bearing_intersection(heading, self, inbound_course)
+				.0;
+			if diff_angle(heading, reversal_turn.end.bearing_to(join), Direction::Either).abs()
+				>= Angle::HALF_TURN / 2.0
+				|| join.along_track_distance(self, inbound_course) > Length::ZERO
+			{
+				return None;
+			}
+
+			Some(ProcedureTurn {
+				outbound,
+				outbound_turn,
+				offset_leg,
+				reversal_turn,
+				intercept: GreatCircleSegment::new(reversal_turn.end, join),
+				inbound: GreatCircleSegment::new(join, self),
+			})
+		};
+		let extent = |turn: &ProcedureTurn| {
+			let arc = turn.reversal_turn;
+			arc.centre
+				.arc_polyline(
+					arc.radius,
+					arc.centre.bearing_to(arc.start),
+					arc.centre.bearing_to(arc.end),
+					arc.direction,
+					MAX_CHORD_ERROR,
+				)
+				.into_iter()
+				.map(|point| self.distance_to(point))
+				.fold(Length::ZERO, Length::max)
+		};
+
+		let shortest = build(Length::ZERO)?;
+		if extent(&shortest) > limit_distance {
+			return None;
+		}
+
+		// The extent of the turn grows with the length of the outbound leg, so bisect for the longest that fits
+		let mut low = Length::ZERO;
+		let mut high = limit_distance;
+		for _ in 0..50 {
+			let middle = (low + high) / 2.0;
+			match build(middle) {
+				Some(turn) if extent(&turn) <= limit_distance => low = middle,
+				_ => high = middle,
+			}
+		}
+
+		build(low)
+	}
+
+	/// Returns the base turn at fix `self` for `inbound_course`, flying `outbound_length` outbound on a course
+	/// `outbound_offset` from the reciprocal of the inbound course, before turning back in `direction` (`Left` or
+	/// `Right`) at `radius`.
+	///
+	/// The outbound course is offset to the side that lets the turn come back towards the inbound course. Returns
+	/// `None` if the turn cannot join the inbound course before the fix
+	pub fn base_turn(
+		self, inbound_course: Angle, direction: Direction, outbound_offset: Angle, outbound_length: Length,
+		radius: Length,
+	) -> Option<BaseTurn> {
+		let direction = turn_direction(direction);
+		let outbound_course = match direction {
+			Direction::Left => inbound_course + Angle::HALF_TURN + outbound_offset.abs(),
+			_ => inbound_course + Angle::HALF_TURN - outbound_offset.abs(),
+		};
+
+		let outbound = GreatCircleSegment::new(self, self.bearing_distance(outbound_course, outbound_length));
+		let transition = outbound.end.turn_to_intercept(
+			outbound.end_course(),
+			radius,
+			direction,
+			self,
+			inbound_course,
+			BASE_TURN_INTERCEPT_ANGLE,
+		)?;
+
+		let join = transition.intercept.end;
+		if join.along_track_distance(self, inbound_course) > Length::ZERO {
+			return None;
+		}
+
+		Some(BaseTurn {
+			outbound,
+			turn: transition.turn,
+			intercept: transition.intercept,
+			inbound: GreatCircleSegment::new(join, self),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	fn assert_continuous(segments: &[Segment], fix: Coordinates) {
+		assert_eq!(segments[0].start(), fix);
+		assert_eq!(segments[segments.len() - 1].end(), fix);
+		for pair in segments.windows(2) {
+			assert_eq!(pair[0].end(), pair[1].start());
+		}
+	}
+
+	#[test]
+	fn test_procedure_turn() {
+		let fix = Coordinates::new(-33.9, 151.2);
+		let inbound_course = Angle::new::<degree>(340.0);
+		let limit = Length::new::<nautical_mile>(10.0);
+		let radius = Length::new::<nautical_mile>(1.5);
+
+		let turn = fix
+			.procedure_turn(
+				inbound_course,
+				Direction::Right,
+				limit,
+				Length::new::<nautical_mile>(3.0),
+				radius,
+			)
+			.unwrap();
+
+		assert_continuous(&turn.segments(), fix);
+		assert!(turn.outbound.length() > Length::ZERO);
+		assert_uom_eq!(turn.outbound.start_course(), Angle::new::<degree>(160.0), 1e-6);
+		assert_uom_eq!(turn.outbound_turn.sweep(), Angle::new::<degree>(45.0), 1e-3);
+		assert_uom_eq!(turn.reversal_turn.sweep(), Angle::new::<degree>(-180.0), 1e-3);
+		assert_uom_eq!(
+			diff_angle(inbound_course, turn.intercept.start_course(), Direction::Either),
+			Angle::new::<degree>(45.0),
+			1e-2
+		);
+		assert_uom_eq!(
+			turn.intercept.end.cross_track_distance(fix, inbound_course),
+			Length::ZERO,
+			1e-3
+		);
+
+		// With an offset leg of twice the radius, the offset leg and the 180 degree turn move the aircraft equally far
+		// across the inbound course, so it rolls out as far from the course as the 45 degree turn took it
+		let (sin, cos) = (0.5f64.sqrt(), 0.5f64.sqrt());
+		let offset_leg = Length::new::<nautical_mile>(3.0);
+		let rollout = radius * (1.0 - cos);
+		assert_uom_eq!(turn.intercept.length(), rollout / sin, 10.0);
+		assert_uom_eq!(
+			turn.inbound.length(),
+			turn.outbound.length() + radius * sin + offset_leg * cos + 2.0 * radius * sin - rollout / sin * cos,
+			10.0
+		);
+
+		let furthest = (0..=180)
+			.map(|i| {
+				let arc = turn.reversal_turn;
+				fix.distance_to(arc.centre.bearing_distance(
+					arc.centre.bearing_to(arc.start) - Angle::new::<degree>(i as f64),
+					radius,
+				))
+			})
+			.fold(Length::ZERO, Length::max);
+		assert_uom_eq!(furthest, limit, 5.0);
+
+		assert!(fix
+			.procedure_turn(
+				inbound_course,
+				Direction::Right,
+				Length::new::<nautical_mile>(3.0),
+				Length::new::<nautical_mile>(3.0),
+				radius,
+			)
+			.is_none());
+
+		// Too short an offset leg rolls out beyond the inbound course
+		assert!(fix
+			.procedure_turn(
+				inbound_course,
+				Direction::Right,
+				limit,
+				Length::new::<nautical_mile>(2.0),
+				radius,
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn test_base_turn() {
+		let fix = Coordinates::new(51.5, -0.1);
+		let inbound_course = Angle::ZERO;
+		let radius = Length::new::<nautical_mile>(2.0);
+
+		for direction in [Direction::Left, Direction::Right] {
+			let turn = fix
+				.base_turn(
+					inbound_course,
+					direction,
+					Angle::new::<degree>(30.0),
+					Length::new::<nautical_mile>(8.0),
+					radius,
+				)
+				.unwrap();
+
+			assert_continuous(&turn.segments(), fix);
+			assert_eq!(turn.turn.direction, direction);
+			assert_uom_eq!(
+				turn.intercept.end.cross_track_distance(fix, inbound_course),
+				Length::ZERO,
+				1e-3
+			);
+			assert!(turn.intercept.end.along_track_distance(fix, inbound_course) < Length::ZERO);
+		}
+	}
+}
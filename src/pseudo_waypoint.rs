@@ -0,0 +1,92 @@
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{path::Path, Coordinates};
+
+/// A distance along a path, measured from one of its ends
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathDistance {
+	FromStart(Length),
+	FromEnd(Length),
+}
+
+/// A point placed along a path by its distance, such as a top of climb or top of descent
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PseudoWaypoint {
+	pub position: Coordinates,
+	/// Course of the path at the point
+	pub course: Angle,
+	/// Index of the segment of the path the point lies on
+	pub segment: usize,
+	/// Distance along the path from its start to the point
+	pub distance: Length,
+}
+
+impl Path {
+	/// Returns the pseudo waypoint at `distance` along the path. Returns `None` if the distance lies beyond either end
+	/// of the path
+	pub fn pseudo_waypoint(&self, distance: PathDistance) -> Option<PseudoWaypoint> {
+		let distance = match distance {
+			PathDistance::FromStart(distance) => distance,
+			PathDistance::FromEnd(distance) => self.length() - distance,
+		};
+		if distance < Length::ZERO || distance > self.length() {
+			return None;
+		}
+
+		Some(PseudoWaypoint {
+			position: self.point_at(distance)?,
+			course: self.course_at(distance)?,
+			segment: self.segment_at(distance)?,
+			distance,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::{assert_uom_eq, path::turn_path};
+
+	#[test]
+	fn test_pseudo_waypoint() {
+		let (path, turn) = turn_path(Length::new::<nautical_mile>(5.0));
+		let to = path.segments()[2].end();
+
+		// Top of descent 20 nm before the end of the path
+		let descent = path
+			.pseudo_waypoint(PathDistance::FromEnd(Length::new::<nautical_mile>(20.0)))
+			.unwrap();
+		assert_eq!(descent.segment, 2);
+		assert_uom_eq!(descent.course, Angle::ZERO, 1e-9);
+		assert_uom_eq!(descent.position.long, Angle::new::<degree>(1.0), 1e-9);
+		assert_uom_eq!(
+			descent.position.distance_to(to),
+			Length::new::<nautical_mile>(20.0),
+			1e-3
+		);
+
+		// Halfway around the turn
+		let distance = path.segment_start_distance(1) + turn.arc.length() / 2.0;
+		let midturn = path.pseudo_waypoint(PathDistance::FromStart(distance)).unwrap();
+		assert_eq!(midturn.segment, 1);
+		assert_uom_eq!(midturn.distance, distance, 1e-9);
+		assert_uom_eq!(midturn.course, Angle::new::<degree>(45.0), 1e-3);
+		assert_uom_eq!(turn.arc.centre.distance_to(midturn.position), turn.arc.radius, 1e-3);
+
+		let end = path.pseudo_waypoint(PathDistance::FromEnd(Length::ZERO)).unwrap();
+		assert_eq!(end.segment, 2);
+		assert_uom_eq!(end.position.lat, to.lat, 1e-9);
+
+		assert!(path
+			.pseudo_waypoint(PathDistance::FromStart(path.length() * 1.01))
+			.is_none());
+		assert!(path
+			.pseudo_waypoint(PathDistance::FromEnd(-Length::new::<nautical_mile>(1.0)))
+			.is_none());
+	}
+}
@@ -0,0 +1,264 @@
+use uom::si::f64::{Angle, Length};
+
+use crate::{
+	constants::EARTH_RADIUS,
+	utility::{clamp_angle_cw, diff_angle},
+	Coordinates,
+	Direction,
+};
+
+/// The shortest great circle path from `start` to `end`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GreatCircleSegment {
+	pub start: Coordinates,
+	pub end: Coordinates,
+}
+
+/// A path along the circle at `radius` around `centre`, from `start` to `end`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ArcSegment {
+	pub centre: Coordinates,
+	pub radius: Length,
+	pub start: Coordinates,
+	pub end: Coordinates,
+	/// Direction of travel around the centre - `Left` for anticlockwise, or `Right` for clockwise. **Note** that
+	/// `Either` is treated as `Right`
+	pub direction: Direction,
+}
+
+/// A single piece of a path
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Segment {
+	GreatCircle(GreatCircleSegment),
+	Arc(ArcSegment),
+}
+
+impl Segment {
+	pub fn start(&self) -> Coordinates {
+		match self {
+			Segment::GreatCircle(segment) => segment.start,
+			Segment::Arc(segment) => segment.start,
+		}
+	}
+
+	pub fn end(&self) -> Coordinates {
+		match self {
+			Segment::GreatCircle(segment) => segment.end,
+			Segment::Arc(segment) => segment.end,
+		}
+	}
+
+	pub fn length(&self) -> Length {
+		match self {
+			Segment::GreatCircle(segment) => segment.length(),
+			Segment::Arc(segment) => segment.length(),
+		}
+	}
+
+	/// Returns the point `distance` along the segment from its start
+	pub fn point_at(&self, distance: Length) -> Coordinates {
+		match self {
+			Segment::GreatCircle(segment) => segment.point_at(distance),
+			Segment::Arc(segment) => segment.point_at(distance),
+		}
+	}
+
+	/// Returns the course of the segment at `point`, which should lie on the segment
+	pub fn course_at(&self, point: Coordinates) -> Angle {
+		match self {
+			Segment::GreatCircle(segment) => segment.course_at(point),
+			Segment::Arc(segment) => segment.course_at(point),
+		}
+	}
+
+	/// Returns the distance along the segment from its start to the point abeam `point`
+	pub fn along_track_distance(&self, point: Coordinates) -> Length {
+		match self {
+			Segment::GreatCircle(segment) => segment.along_track_distance(point),
+			Segment::Arc(segment) => segment.along_track_distance(point),
+		}
+	}
+
+	/// Returns the distance of `point` from the segment, positive to the right and negative to the left
+	pub fn cross_track_distance(&self, point: Coordinates) -> Length {
+		match self {
+			Segment::GreatCircle(segment) => segment.cross_track_distance(point),
+			Segment::Arc(segment) => segment.cross_track_distance(point),
+		}
+	}
+}
+
+impl From<GreatCircleSegment> for Segment {
+	fn from(segment: GreatCircleSegment) -> Self { Segment::GreatCircle(segment) }
+}
+
+impl From<ArcSegment> for Segment {
+	fn from(segment: ArcSegment) -> Self { Segment::Arc(segment) }
+}
+
+impl GreatCircleSegment {
+	pub fn new(start: Coordinates, end: Coordinates) -> Self { Self { start, end } }
+
+	pub fn length(&self) -> Length { self.start.distance_to(self.end) }
+
+	/// Returns the course at the start of the segment
+	pub fn start_course(&self) -> Angle { self.start.bearing_to(self.end) }
+
+	/// Returns the course at the end of the segment
+	pub fn end_course(&self) -> Angle { clamp_angle_cw(self.end.bearing_to(self.start) + Angle::HALF_TURN) }
+
+	/// Returns the point `distance` along the great circle from `start`
+	pub fn point_at(&self, distance: Length) -> Coordinates {
+		self.start.bearing_distance(self.start_course(), distance)
+	}
+
+	/// Returns the course of the great circle at `point`, which should lie on the great circle
+	pub fn course_at(&self, point: Coordinates) -> Angle {
+		// Measure from whichever end is further away, as the bearing to a coincident point is undefined
+		if self.along_track_distance(point) < self.length() / 2.0 {
+			point.bearing_to(self.end)
+		} else {
+			clamp_angle_cw(point.bearing_to(self.start) + Angle::HALF_TURN)
+		}
+	}
+
+	/// Returns the distance along the great circle from `start` to the point abeam `point`
+	pub fn along_track_distance(&self, point: Coordinates) -> Length {
+		point.along_track_distance(self.start, self.start_course())
+	}
+
+	/// Returns the distance of `point` from the great circle, positive to the right and negative to the left
+	pub fn cross_track_distance(&self, point: Coordinates) -> Length {
+		point.cross_track_distance(self.start, self.start_course())
+	}
+}
+
+impl ArcSegment {
+	/// Returns the angle turned through around the centre from `start` to `end` - positive when turning clockwise, or
+	/// negative when turning anticlockwise
+	pub fn sweep(&self) -> Angle {
+		diff_angle(
+			self.centre.bearing_to(self.start),
+			self.centre.bearing_to(self.end),
+			self.turn_direction(),
+		)
+	}
+
+	pub fn length(&self) -> Length {
+		let radial_distance: Angle = (self.radius / EARTH_RADIUS).into();
+
+		EARTH_RADIUS * radial_distance.sin() * self.sweep().abs().value
+	}
+
+	/// Returns the point `distance` along the arc from `start`
+	pub fn point_at(&self, distance: Length) -> Coordinates {
+		let radial_distance: Angle = (self.radius / EARTH_RADIUS).into();
+		let turned: Angle = (distance / (EARTH_RADIUS * radial_distance.sin())).into();
+		let start_radial = self.centre.bearing_to(self.start);
+
+		match self.turn_direction() {
+			Direction::Left => self.centre.bearing_distance(start_radial - turned, self.radius),
+			_ => self.centre.bearing_distance(start_radial + turned, self.radius),
+		}
+	}
+
+	/// Returns the course at the start of the segment
+	pub fn start_course(&self) -> Angle { self.course_at(self.start) }
+
+	/// Returns the course at the end of the segment
+	pub fn end_course(&self) -> Angle { self.course_at(self.end) }
+
+	/// Returns the course tangent to the arc at `point`, which should lie on the arc
+	pub fn course_at(&self, point: Coordinates) -> Angle {
+		let to_centre = point.bearing_to(self.centre);
+		match self.turn_direction() {
+			Direction::Left => clamp_angle_cw(to_centre + Angle::HALF_TURN / 2.0),
+			_ => clamp_angle_cw(to_centre - Angle::HALF_TURN / 2.0),
+		}
+	}
+
+	pub(crate) fn turn_direction(&self) -> Direction { turn_direction(self.direction) }
+}
+
+/// Resolves the direction of a turn, treating `Either` as `Right`
+pub(crate) fn turn_direction(direction: Direction) -> Direction {
+	match direction {
+		Direction::Left => Direction::Left,
+		_ => Direction::Right,
+	}
+}
+
+/// Returns the bearing, relative to the current course, of the centre of a turn in `direction`, treating `Either` as
+/// `Right`
+pub(crate) fn quarter_turn(direction: Direction) -> Angle {
+	match direction {
+		Direction::Left => -Angle::HALF_TURN / 2.0,
+		_ => Angle::HALF_TURN / 2.0,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use uom::{
+		si::{angle::degree, length::nautical_mile},
+		ConstZero,
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_arc_segment() {
+		let centre = Coordinates::new(0.0, 0.0);
+		let radius = Length::new::<nautical_mile>(10.0);
+		let arc = ArcSegment {
+			centre,
+			radius,
+			start: centre.bearing_distance(Angle::new::<degree>(0.0), radius),
+			end: centre.bearing_distance(Angle::new::<degree>(90.0), radius),
+			direction: Direction::Right,
+		};
+
+		assert_uom_eq!(arc.sweep(), Angle::new::<degree>(90.0));
+		assert_uom_eq!(arc.length(), radius * PI / 2.0, 1e-1);
+		assert_uom_eq!(arc.start_course(), Angle::new::<degree>(90.0));
+		assert_uom_eq!(arc.end_course(), Angle::new::<degree>(180.0));
+
+		let point = arc.point_at(arc.length() / 3.0);
+		assert_uom_eq!(centre.bearing_to(point), Angle::new::<degree>(30.0), 1e-9);
+		assert_uom_eq!(arc.along_track_distance(point), arc.length() / 3.0, 1e-3);
+
+		let arc = ArcSegment {
+			direction: Direction::Left,
+			..arc
+		};
+		assert_uom_eq!(arc.sweep(), Angle::new::<degree>(-270.0));
+		assert_uom_eq!(arc.length(), radius * PI * 1.5, 1.0);
+		assert_uom_eq!(arc.start_course(), Angle::new::<degree>(270.0));
+		assert_uom_eq!(
+			diff_angle(arc.end_course(), Angle::ZERO, Direction::Either),
+			Angle::ZERO
+		);
+	}
+
+	#[test]
+	fn test_great_circle_segment() {
+		let segment = GreatCircleSegment::new(Coordinates::new(0.0, 170.0), Coordinates::new(0.0, -170.0));
+
+		assert_uom_eq!(segment.start_course(), Angle::new::<degree>(90.0));
+		assert_uom_eq!(segment.end_course(), Angle::new::<degree>(90.0));
+		assert_uom_eq!(segment.length(), EARTH_RADIUS * Angle::new::<degree>(20.0).value, 1e-3);
+
+		let point = segment.point_at(segment.length() / 2.0);
+		assert_uom_eq!(point.long.abs(), Angle::new::<degree>(180.0), 1e-9);
+		assert_uom_eq!(segment.course_at(point), Angle::new::<degree>(90.0), 1e-9);
+		assert_uom_eq!(segment.along_track_distance(point), segment.length() / 2.0, 1e-3);
+		assert_uom_eq!(
+			segment.cross_track_distance(Coordinates::new(-0.1, 180.0)),
+			EARTH_RADIUS * Angle::new::<degree>(0.1).value,
+			1e-3
+		);
+	}
+}
@@ -0,0 +1,227 @@
+use std::marker::PhantomData;
+
+use uom::{si::f64::Length, ConstZero};
+
+use crate::{path::Path, segment::Segment, transition::anticipation_distance, Coordinates};
+
+/// Largest distance between a waypoint and the end of a segment for the waypoint to be treated as lying there
+const WAYPOINT_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+/// How far an aircraft has progressed along the leg to a waypoint
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LegProgress {
+	/// Tracking towards the waypoint
+	Tracking,
+	/// On the turn of a fly-by waypoint, before crossing its bisector
+	Turning,
+	/// The leg is complete, and the next leg should become active
+	Sequence,
+}
+
+impl Path {
+	/// Returns the progress of an aircraft at `position` along the leg to the waypoint at `waypoint` in
+	/// `waypoint_distances`. Returns `None` if there is no such waypoint, or if the path is empty.
+	///
+	/// A waypoint is sequenced once the aircraft passes abeam it along the path. A fly-by waypoint lies abeam the
+	/// middle of its turn (see `push_fly_by_turn`), so is sequenced when the aircraft crosses the bisector of the
+	/// turn, and the aircraft is turning from the start of the turn built into the path. A waypoint at the end of a
+	/// segment, such as a fly-over waypoint or the last waypoint, is sequenced once the aircraft passes abeam the end
+	/// of the segment. An aircraft which overshoots a fly-by waypoint without crossing the bisector, as can happen on
+	/// the outside of a sharp turn, is sequenced once abeam the waypoint on the leg before the turn, so it can recover
+	/// onto the next leg
+	pub fn leg_progress(&self, waypoint: usize, position: Coordinates) -> Option<LegProgress> {
+		let distance = *self.waypoint_distances().get(waypoint)?;
+		let segments = self.segments();
+
+		// The segment which the waypoint lies on or at the end of
+		let index = (0..segments.len())
+			.find(|index| self.segment_start_distance(index + 1) >= distance - WAYPOINT_TOLERANCE)
+			.unwrap_or(segments.len().checked_sub(1)?);
+		let start = self.segment_start_distance(index);
+		let segment = &segments[index];
+
+		let along = segment.along_track_distance(position);
+		if start + along >= distance {
+			return Some(LegProgress::Sequence);
+		}
+
+		match segment {
+			Segment::Arc(turn) if distance < start + segment.length() - WAYPOINT_TOLERANCE => {
+				if let Some(Segment::GreatCircle(inbound)) = index.checked_sub(1).map(|index| &segments[index]) {
+					let anticipation = anticipation_distance(turn.radius, turn.sweep()).unwrap_or(Length::ZERO);
+					if inbound.along_track_distance(position) >= inbound.length() + anticipation {
+						return Some(LegProgress::Sequence);
+					}
+				}
+
+				if along >= Length::ZERO {
+					Some(LegProgress::Turning)
+				} else {
+					Some(LegProgress::Tracking)
+				}
+			},
+			_ => Some(LegProgress::Tracking),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{
+		angle::degree,
+		f64::{Angle, Velocity},
+		length::nautical_mile,
+		velocity::knot,
+	};
+
+	use super::*;
+	use crate::{segment::GreatCircleSegment, transition::FlyByTurn, Direction};
+
+	/// Returns the fly-by turn at `waypoint` between the legs from `from` and to `next`, at 240 kt and 25 degrees of
+	/// bank
+	fn fly_by_turn(from: Coordinates, waypoint: Coordinates, next: Coordinates) -> FlyByTurn {
+		waypoint
+			.fly_by_turn_for_speed(
+				GreatCircleSegment::new(from, waypoint).end_course(),
+				waypoint.bearing_to(next),
+				Velocity::new::<knot>(240.0),
+				Angle::new::<degree>(25.0),
+				Direction::Either,
+			)
+			.unwrap()
+	}
+
+	/// Returns the path from `from` to `next`, flying `turn` at the waypoint between them
+	fn fly_by_path(from: Coordinates, turn: &FlyByTurn, next: Coordinates) -> Path {
+		let mut path = Path::from_waypoints(&[from]);
+		path.push(GreatCircleSegment::new(from, turn.arc.start));
+		path.push_fly_by_turn(turn);
+		path.push(GreatCircleSegment::new(turn.arc.end, next));
+		path.push_waypoint();
+
+		path
+	}
+
+	#[test]
+	fn test_fly_by_sequencing() {
+		let from = Coordinates::new(0.0, 179.0);
+		let waypoint = Coordinates::new(0.0, -179.0);
+		let next = Coordinates::new(2.0, -179.0);
+		let turn = fly_by_turn(from, waypoint, next);
+		let path = fly_by_path(from, &turn, next);
+		let progress = |position: Coordinates| path.leg_progress(1, position).unwrap();
+		let before = |distance: Length| waypoint.bearing_distance(Angle::new::<degree>(270.0), distance);
+
+		assert_eq!(progress(Coordinates::new(0.0, 179.9)), LegProgress::Tracking);
+		assert_eq!(
+			progress(before(turn.anticipation_distance * 1.1)),
+			LegProgress::Tracking
+		);
+		assert_eq!(progress(before(turn.anticipation_distance * 0.9)), LegProgress::Turning);
+
+		// Either side of the bisector, inside the turn
+		let offset = Length::new::<nautical_mile>(0.1);
+		assert_eq!(
+			progress(waypoint.bearing_distance(Angle::new::<degree>(300.0), offset)),
+			LegProgress::Turning
+		);
+		assert_eq!(
+			progress(waypoint.bearing_distance(Angle::new::<degree>(330.0), offset)),
+			LegProgress::Sequence
+		);
+
+		assert_eq!(path.leg_progress(0, from), Some(LegProgress::Sequence));
+		assert_eq!(path.leg_progress(3, from), None);
+	}
+
+	#[test]
+	fn test_turn_from_path() {
+		let from = Coordinates::new(0.0, 0.0);
+		let waypoint = Coordinates::new(0.0, 1.0);
+		let next = Coordinates::new(1.0, 1.0);
+		let turn = fly_by_turn(from, waypoint, next);
+		let path = fly_by_path(from, &turn, next);
+
+		// A wider turn built into the path begins further from the waypoint
+		let wide = waypoint
+			.fly_by_turn(
+				Angle::new::<degree>(90.0),
+				Angle::ZERO,
+				turn.arc.radius * 2.0,
+				Direction::Either,
+			)
+			.unwrap();
+		let wide_path = fly_by_path(from, &wide, next);
+
+		let position = waypoint.bearing_distance(Angle::new::<degree>(270.0), turn.anticipation_distance * 1.5);
+		assert_eq!(path.leg_progress(1, position), Some(LegProgress::Tracking));
+		assert_eq!(wide_path.leg_progress(1, position), Some(LegProgress::Turning));
+	}
+
+	#[test]
+	fn test_fly_over_sequencing() {
+		let from = Coordinates::new(10.0, 20.0);
+		let waypoint = Coordinates::new(11.0, 20.0);
+		let next = Coordinates::new(11.0, 21.0);
+		let transition = waypoint
+			.fly_over_transition(
+				Angle::ZERO,
+				waypoint.bearing_to(next),
+				Length::new::<nautical_mile>(2.0),
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+
+		let mut path = Path::from_waypoints(&[from, waypoint]);
+		path.push(transition.turn);
+		path.push(transition.intercept);
+		path.push(GreatCircleSegment::new(transition.intercept.end, next));
+		path.push_waypoint();
+
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(10.999, 20.001)),
+			Some(LegProgress::Tracking)
+		);
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(11.001, 19.9)),
+			Some(LegProgress::Sequence)
+		);
+
+		let path = Path::from_waypoints(&[from, waypoint]);
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(10.999, 20.0)),
+			Some(LegProgress::Tracking)
+		);
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(11.001, 20.0)),
+			Some(LegProgress::Sequence)
+		);
+	}
+
+	#[test]
+	fn test_overshoot_sequencing() {
+		let from = Coordinates::new(0.0, 0.0);
+		let waypoint = Coordinates::new(0.0, 1.0);
+		// A 150 degree turn back to the left
+		let next = waypoint.bearing_distance(Angle::new::<degree>(300.0), Length::new::<nautical_mile>(30.0));
+		let path = fly_by_path(from, &fly_by_turn(from, waypoint, next), next);
+
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(0.0, 0.999)),
+			Some(LegProgress::Turning)
+		);
+		assert_eq!(
+			path.leg_progress(1, Coordinates::new(0.0, 1.01)),
+			Some(LegProgress::Sequence)
+		);
+
+		// Wide on the outside of the turn, past the waypoint but not yet across the bisector
+		let position = Coordinates::new(-0.05, 1.01);
+		assert_eq!(path.leg_progress(1, position), Some(LegProgress::Sequence));
+	}
+}
@@ -0,0 +1,142 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use uom::si::f64::Length;
+
+use crate::Coordinates;
+
+/// A run of points between two retained points, along with the point furthest from the great circle segment joining
+/// them
+struct Span {
+	start: usize,
+	end: usize,
+	furthest: usize,
+	distance: Length,
+}
+
+impl PartialEq for Span {
+	fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for Span {}
+
+impl PartialOrd for Span {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Span {
+	fn cmp(&self, other: &Self) -> Ordering { self.distance.value.total_cmp(&other.distance.value) }
+}
+
+/// Simplifies the great circle polyline `points` using the Douglas-Peucker algorithm, returning a subset of the points
+/// such that no removed point is further than `tolerance` from the simplified polyline.
+///
+/// The first and last points are always kept. If `max_points` is given, at most that many points (but never fewer than
+/// two) are returned, keeping the points which deviate furthest from the simplified polyline first.
+pub fn simplify(points: &[Coordinates], tolerance: Length, max_points: Option<usize>) -> Vec<Coordinates> {
+	if points.len() <= 2 {
+		return points.to_vec();
+	}
+
+	let max_points = max_points.unwrap_or(usize::MAX).max(2);
+	let mut keep = vec![false; points.len()];
+	keep[0] = true;
+	keep[points.len() - 1] = true;
+	let mut kept = 2;
+
+	let mut spans = BinaryHeap::new();
+	spans.extend(span(points, 0, points.len() - 1));
+
+	while let Some(span) = spans.pop() {
+		if kept >= max_points || span.distance <= tolerance {
+			break;
+		}
+
+		keep[span.furthest] = true;
+		kept += 1;
+
+		spans.extend(self::span(points, span.start, span.furthest));
+		spans.extend(self::span(points, span.furthest, span.end));
+	}
+
+	points
+		.iter()
+		.zip(keep)
+		.filter_map(|(&point, keep)| keep.then_some(point))
+		.collect()
+}
+
+fn span(points: &[Coordinates], start: usize, end: usize) -> Option<Span> {
+	(start + 1..end)
+		.map(|index| (index, segment_distance(points[index], points[start], points[end])))
+		.max_by(|a, b| a.1.value.total_cmp(&b.1.value))
+		.map(|(furthest, distance)| Span {
+			start,
+			end,
+			furthest,
+			distance,
+		})
+}
+
+/// Returns the distance from `point` to the closest point on the great circle segment from `start` to `end`
+fn segment_distance(point: Coordinates, start: Coordinates, end: Coordinates) -> Length {
+	let length = start.distance_to(end);
+	let course = start.bearing_to(end);
+	let along_track = point.along_track_distance(start, course);
+
+	if length.value == 0.0 || along_track.value <= 0.0 {
+		point.distance_to(start)
+	} else if along_track >= length {
+		point.distance_to(end)
+	} else {
+		point.cross_track_distance(start, course).abs()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::length::nautical_mile;
+
+	use super::*;
+
+	#[test]
+	fn test_simplify_collinear() {
+		let points: Vec<Coordinates> = (0..=10).map(|i| Coordinates::new(0.0, 175.0 + i as f64)).collect();
+
+		let simplified = simplify(&points, Length::new::<nautical_mile>(0.1), None);
+		assert_eq!(simplified, vec![points[0], points[10]]);
+	}
+
+	#[test]
+	fn test_simplify_tolerance() {
+		let points = vec![
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.26, 1.0),
+			Coordinates::new(0.5, 2.0),
+			Coordinates::new(0.24, 3.0),
+			Coordinates::new(0.0, 4.0),
+		];
+
+		let simplified = simplify(&points, Length::new::<nautical_mile>(1.0), None);
+		assert_eq!(simplified, vec![points[0], points[2], points[4]]);
+
+		let simplified = simplify(&points, Length::new::<nautical_mile>(0.1), None);
+		assert_eq!(simplified, points);
+	}
+
+	#[test]
+	fn test_simplify_max_points() {
+		let points = vec![
+			Coordinates::new(0.0, 0.0),
+			Coordinates::new(0.2, 1.0),
+			Coordinates::new(0.0, 2.0),
+			Coordinates::new(1.0, 3.0),
+			Coordinates::new(0.0, 4.0),
+		];
+
+		let simplified = simplify(&points, Length::new::<nautical_mile>(0.1), Some(3));
+		assert_eq!(simplified, vec![points[0], points[3], points[4]]);
+
+		let simplified = simplify(&points, Length::new::<nautical_mile>(0.1), Some(0));
+		assert_eq!(simplified, vec![points[0], points[4]]);
+	}
+}
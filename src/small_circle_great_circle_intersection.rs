@@ -8,14 +8,14 @@ use uom::{
 
 use crate::{
 	constants::{EARTH_RADIUS, FULL_RATIO},
-	spherical::XYZ,
+	spherical::Xyz,
 	utility::diff_angle,
 	Coordinates,
 	Direction::Either,
 };
 
 pub fn solve_with_permutations(
-	small_circle_xyz: XYZ, ns: XYZ, small_circle_radius: Length, permutations: [[usize; 3]; 3],
+	small_circle_xyz: Xyz, ns: Xyz, small_circle_radius: Length, permutations: [[usize; 3]; 3],
 ) -> Option<(Coordinates, Coordinates)> {
 	let mut permutation = permutations[0];
 
@@ -37,7 +37,7 @@ pub fn solve_with_permutations(
 		/ denominator;
 	let c = (ns[permutation[1]] * (small_circle_radius * small_circle_radius - 2.0 * EARTH_RADIUS * EARTH_RADIUS))
 		/ 2.0 / denominator;
-	let d = -(ns[permutation[1]] * small_circle_xyz[permutation[0]]
+	let d = (ns[permutation[1]] * small_circle_xyz[permutation[0]]
 		- ns[permutation[0]] * small_circle_xyz[permutation[1]])
 		/ denominator;
 
@@ -61,8 +61,8 @@ pub fn solve_with_permutations(
 	result2[permutation[2]] = c + d * result2[permutation[0]];
 
 	Some((
-		XYZ::new(result1[0], result1[1], result1[2]).into(),
-		XYZ::new(result2[0], result2[1], result2[2]).into(),
+		Xyz::new(result1[0], result1[1], result1[2]).into(),
+		Xyz::new(result2[0], result2[1], result2[2]).into(),
 	))
 }
 
@@ -70,21 +70,25 @@ impl Coordinates {
 	pub fn small_circle_great_circle_intersection(
 		self, radius: Length, great_circle_reference: Coordinates, great_circle_bearing: Angle,
 	) -> Option<(Coordinates, Coordinates)> {
-		let small_circle_xyz: XYZ = self.into();
-		let great_circle_xyz: XYZ = great_circle_reference.into();
+		let small_circle_xyz: Xyz = self.into();
+		let great_circle_xyz: Xyz = great_circle_reference.into();
 
 		let v = great_circle_reference.calculate_v(great_circle_bearing);
 
-		let normal_vector = XYZ::new(
+		let normal_vector = Xyz::new(
 			great_circle_xyz[1] * v.z - great_circle_xyz[2] * v.y,
 			great_circle_xyz[2] * v.x - great_circle_xyz[0] * v.z,
 			great_circle_xyz[0] * v.y - great_circle_xyz[1] * v.x,
 		);
 
+		// The circle is solved as the intersection of the sphere with the sphere of the chord length around the centre
+		let half_angle: Angle = (radius / EARTH_RADIUS / 2.0).into();
+		let chord = 2.0 * EARTH_RADIUS * half_angle.sin();
+
 		solve_with_permutations(
 			small_circle_xyz,
 			normal_vector,
-			radius,
+			chord,
 			[[0, 1, 2], [2, 0, 1], [1, 2, 0]],
 		)
 	}
@@ -100,10 +104,10 @@ impl Coordinates {
 	pub fn first_small_circle_intersection(
 		self, radius: Length, bearing_reference: Coordinates, bearing: Angle,
 	) -> Option<Coordinates> {
-		return match self.small_circle_great_circle_intersection(radius, bearing_reference, bearing) {
+		match self.small_circle_great_circle_intersection(radius, bearing_reference, bearing) {
 			None => None,
 			Some(intercepts) => {
-				return if bearing_reference.distance_to(self) <= radius {
+				if bearing_reference.distance_to(self) <= radius {
 					// The great circle reference is inside the circle, use the intercept which is in-front of the great
 					// circle reference as per the great circle bearing
 					if diff_angle(bearing, bearing_reference.bearing_to(intercepts.0), Either).abs()
@@ -129,9 +133,9 @@ impl Coordinates {
 					} else {
 						Some(intercepts.1)
 					}
-				};
+				}
 			},
-		};
+		}
 	}
 
 	/// Returns the **closest** intersection to occur between a circle at `radius` around `self` with a great circle
@@ -144,18 +148,16 @@ impl Coordinates {
 	pub fn closest_small_circle_intersection(
 		self, radius: Length, great_circle_reference: Coordinates, great_circle_bearing: Angle,
 	) -> Option<Coordinates> {
-		return match self.small_circle_great_circle_intersection(radius, great_circle_reference, great_circle_bearing) {
+		match self.small_circle_great_circle_intersection(radius, great_circle_reference, great_circle_bearing) {
 			None => None,
 			Some(intercepts) => {
-				return if great_circle_reference.distance_to(intercepts.0)
-					< great_circle_reference.distance_to(intercepts.1)
-				{
+				if great_circle_reference.distance_to(intercepts.0) < great_circle_reference.distance_to(intercepts.1) {
 					Some(intercepts.0)
 				} else {
 					Some(intercepts.1)
 				}
 			},
-		};
+		}
 	}
 }
 
@@ -195,5 +197,75 @@ mod tests {
 			},
 		}
 	}
+
+	#[test]
+	fn test_small_circle_intersection_oblique() {
+		let centre = Coordinates::new(10.0, 20.0);
+		let radius = Length::new::<nautical_mile>(15.0);
+		let reference = centre.bearing_distance(Angle::new::<degree>(200.0), Length::new::<nautical_mile>(5.0));
+		let bearing = Angle::new::<degree>(200.0);
+
+		let (first, second) = centre
+			.small_circle_great_circle_intersection(radius, reference, bearing)
+			.unwrap();
+		for intersection in [first, second] {
+			assert_uom_eq!(centre.distance_to(intersection), radius, 1e-6);
+			assert_uom_eq!(
+				intersection.cross_track_distance(reference, bearing),
+				Length::ZERO,
+				1e-6
+			);
+		}
+	}
+
+	#[test]
+	fn test_first_small_circle_intersection() {
+		let centre = Coordinates::new(40.0, -75.0);
+		let radius = Length::new::<nautical_mile>(10.0);
+		let reference = centre.bearing_distance(Angle::new::<degree>(180.0), Length::new::<nautical_mile>(20.0));
+
+		// Flying towards the circle, the near side is crossed first
+		let intersection = centre
+			.first_small_circle_intersection(radius, reference, Angle::ZERO)
+			.unwrap();
+		assert_uom_eq!(
+			reference.distance_to(intersection),
+			Length::new::<nautical_mile>(10.0),
+			1e-3
+		);
+		assert_uom_eq!(centre.distance_to(intersection), radius, 1e-3);
+
+		// From inside the circle, the crossing ahead is used
+		let reference = centre.bearing_distance(Angle::new::<degree>(270.0), Length::new::<nautical_mile>(3.0));
+		let intersection = centre
+			.first_small_circle_intersection(radius, reference, Angle::new::<degree>(90.0))
+			.unwrap();
+		assert_uom_eq!(centre.distance_to(intersection), radius, 1e-3);
+		assert_uom_eq!(
+			diff_angle(Angle::new::<degree>(90.0), reference.bearing_to(intersection), Either),
+			Angle::ZERO,
+			1e-6
+		);
+	}
+
+	#[test]
+	fn test_closest_small_circle_intersection() {
+		let centre = Coordinates::new(-35.0, 150.0);
+		let radius = Length::new::<nautical_mile>(10.0);
+		let reference = centre.bearing_distance(Angle::new::<degree>(180.0), Length::new::<nautical_mile>(20.0));
+
+		// The bearing may be reversed without changing the closest intersection
+		for bearing in [Angle::ZERO, Angle::new::<degree>(180.0)] {
+			let intersection = centre
+				.closest_small_circle_intersection(radius, reference, bearing)
+				.unwrap();
+			assert_uom_eq!(
+				reference.distance_to(intersection),
+				Length::new::<nautical_mile>(10.0),
+				1e-3
+			);
+			assert_uom_eq!(centre.distance_to(intersection), radius, 1e-3);
+		}
+	}
 	// TODO: More Tests
 }
@@ -0,0 +1,188 @@
+use std::{f64::consts::FRAC_PI_2, marker::PhantomData};
+
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{
+	constants::{EARTH_RADIUS, FULL_RATIO},
+	utility::diff_angle,
+	Coordinates,
+	Direction,
+};
+
+/// Smallest chord error used, as a chord error of zero would need infinitely many points
+const MIN_CHORD_ERROR: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+/// Largest angle around the centre spanned by a single chord, so that circles smaller than the chord error still keep
+/// their shape
+const MAX_STEP: Angle = Angle {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: FRAC_PI_2,
+};
+
+impl Coordinates {
+	/// Returns points on the circle at `radius` around `self`, clockwise from true north, such that the great circle
+	/// chords between consecutive points (including the last and first) never stray more than `max_chord_error` from
+	/// the circle. `max_chord_error` is raised to at least 1 mm
+	pub fn small_circle_polyline(self, radius: Length, max_chord_error: Length) -> Vec<Coordinates> {
+		let steps = arc_steps(radius, Angle::FULL_TURN, max_chord_error);
+
+		(0..steps)
+			.map(|i| self.bearing_distance(Angle::FULL_TURN * (i as f64 / steps as f64), radius))
+			.collect()
+	}
+
+	/// Returns points on the arc at `radius` around `self`, from bearing `start_bearing` to bearing `end_bearing`
+	/// turning in `direction`, such that the great circle chords between consecutive points never stray more than
+	/// `max_chord_error` from the arc. `Direction::Either` follows the shorter of the two arcs.
+	///
+	/// The first and last points are exactly the ends of the arc. `max_chord_error` is raised to at least 1 mm
+	pub fn arc_polyline(
+		self, radius: Length, start_bearing: Angle, end_bearing: Angle, direction: Direction, max_chord_error: Length,
+	) -> Vec<Coordinates> {
+		let sweep = diff_angle(start_bearing, end_bearing, direction);
+		let steps = arc_steps(radius, sweep.abs(), max_chord_error);
+
+		let mut points: Vec<Coordinates> = (0..steps)
+			.map(|i| self.bearing_distance(start_bearing + sweep * (i as f64 / steps as f64), radius))
+			.collect();
+		points.push(self.bearing_distance(end_bearing, radius));
+
+		points
+	}
+}
+
+/// Returns the number of chords needed to approximate `sweep` of the circle at `radius` within `max_chord_error`
+fn arc_steps(radius: Length, sweep: Angle, max_chord_error: Length) -> usize {
+	let radial_distance: Angle = (radius / EARTH_RADIUS).into();
+	let surface_radius = EARTH_RADIUS * radial_distance.sin();
+	let max_chord_error = max_chord_error.max(MIN_CHORD_ERROR);
+
+	if sweep == Angle::ZERO {
+		return 1;
+	}
+
+	let step: Angle = if surface_radius <= max_chord_error {
+		MAX_STEP
+	} else {
+		(2.0 * (FULL_RATIO - max_chord_error / surface_radius).acos()).min(MAX_STEP)
+	};
+
+	(sweep / step).value.ceil().max(1.0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, length::nautical_mile};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_small_circle_polyline() {
+		let centre = Coordinates::new(45.0, 179.0);
+		let radius = Length::new::<nautical_mile>(10.0);
+		let max_chord_error = Length::new::<nautical_mile>(0.01);
+
+		let points = centre.small_circle_polyline(radius, max_chord_error);
+		assert_eq!(points.len(), 71);
+
+		for (i, &point) in points.iter().enumerate() {
+			assert_uom_eq!(centre.distance_to(point), radius, 1e-3);
+
+			let next = points[(i + 1) % points.len()];
+			let chord_middle = point.bearing_distance(point.bearing_to(next), point.distance_to(next) / 2.0);
+			assert!(radius - centre.distance_to(chord_middle) <= max_chord_error);
+		}
+	}
+
+	#[test]
+	fn test_arc_polyline() {
+		let centre = Coordinates::new(10.0, 20.0);
+		let radius = Length::new::<nautical_mile>(15.0);
+		let max_chord_error = Length::new::<nautical_mile>(0.05);
+
+		let points = centre.arc_polyline(
+			radius,
+			Angle::new::<degree>(350.0),
+			Angle::new::<degree>(80.0),
+			Direction::Right,
+			max_chord_error,
+		);
+		assert_eq!(points[0], centre.bearing_distance(Angle::new::<degree>(350.0), radius));
+		assert_eq!(
+			points[points.len() - 1],
+			centre.bearing_distance(Angle::new::<degree>(80.0), radius)
+		);
+		assert_eq!(points.len(), 11);
+
+		let points = centre.arc_polyline(
+			radius,
+			Angle::new::<degree>(350.0),
+			Angle::new::<degree>(80.0),
+			Direction::Left,
+			max_chord_error,
+		);
+		assert_eq!(points.len(), 30);
+		assert_uom_eq!(
+			centre.bearing_to(points[1]),
+			Angle::new::<degree>(350.0 - 270.0 / 29.0),
+			1e-3
+		);
+
+		let points = centre.arc_polyline(
+			radius,
+			Angle::new::<degree>(350.0),
+			Angle::new::<degree>(80.0),
+			Direction::Either,
+			max_chord_error,
+		);
+		assert_eq!(points.len(), 11);
+	}
+
+	#[test]
+	fn test_zero_chord_error() {
+		let centre = Coordinates::new(10.0, 20.0);
+		let radius = Length::new::<nautical_mile>(1.0);
+
+		let points = centre.small_circle_polyline(radius, Length::ZERO);
+		assert_eq!(points, centre.small_circle_polyline(radius, MIN_CHORD_ERROR));
+		assert_eq!(points, centre.small_circle_polyline(radius, -radius));
+
+		let arc = |max_chord_error: Length| {
+			centre.arc_polyline(
+				radius,
+				Angle::ZERO,
+				Angle::new::<degree>(90.0),
+				Direction::Right,
+				max_chord_error,
+			)
+		};
+		assert_eq!(arc(Length::ZERO), arc(MIN_CHORD_ERROR));
+	}
+
+	#[test]
+	fn test_chord_error_larger_than_circle() {
+		let centre = Coordinates::new(10.0, 20.0);
+		let radius = Length::new::<nautical_mile>(0.1);
+
+		let points = centre.small_circle_polyline(radius, Length::new::<nautical_mile>(1.0));
+		assert_eq!(points.len(), 4);
+
+		let points = centre.arc_polyline(
+			radius,
+			Angle::ZERO,
+			Angle::new::<degree>(100.0),
+			Direction::Right,
+			Length::new::<nautical_mile>(1.0),
+		);
+		assert_eq!(points.len(), 3);
+	}
+}
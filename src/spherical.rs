@@ -11,7 +11,7 @@ use uom::{
 use crate::{constants::EARTH_RADIUS, Coordinates};
 
 pub type Spherical = Matrix1x3<f64>;
-pub type XYZ = Matrix1x3<Length>;
+pub type Xyz = Matrix1x3<Length>;
 pub type UnitVector = Matrix1x3<Ratio>;
 
 impl Coordinates {
@@ -36,7 +36,7 @@ impl Coordinates {
 	pub fn phi_unit_vector(self) -> UnitVector {
 		let phi = self.phi();
 
-		UnitVector::new(phi.sin(), phi.cos(), Ratio::ZERO)
+		UnitVector::new(-phi.sin(), phi.cos(), Ratio::ZERO)
 	}
 
 	pub fn theta(self) -> Angle { Angle::HALF_TURN / 2.0 - self.lat }
@@ -80,8 +80,8 @@ impl From<Coordinates> for Spherical {
 	}
 }
 
-impl From<XYZ> for Coordinates {
-	fn from(xyz: XYZ) -> Self {
+impl From<Xyz> for Coordinates {
+	fn from(xyz: Xyz) -> Self {
 		let x = xyz.x;
 		let y = xyz.y;
 		let z = xyz.z;
@@ -105,7 +105,7 @@ impl From<XYZ> for Coordinates {
 	}
 }
 
-impl From<Coordinates> for XYZ {
+impl From<Coordinates> for Xyz {
 	fn from(coordinates: Coordinates) -> Self {
 		let theta = coordinates.theta();
 		let phi = coordinates.phi();
@@ -0,0 +1,400 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Angle, Length, Ratio, Velocity},
+	ConstZero,
+};
+
+use crate::{
+	segment::{ArcSegment, GreatCircleSegment, Segment},
+	utility::diff_angle,
+	wind::Wind,
+	Coordinates,
+	Direction,
+};
+
+/// Distances shorter than this are treated as zero when deciding whether a termination lies on the legs
+const POSITION_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1.0,
+};
+
+/// How a course or heading leg (ARINC 424 C* or V* leg) is flown
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LegCourse {
+	/// A true course over the ground
+	Course(Angle),
+	/// A true heading flown at `true_airspeed` through `wind`
+	Heading {
+		heading: Angle,
+		true_airspeed: Velocity,
+		wind: Wind,
+	},
+}
+
+/// How an aircraft climbs along an altitude terminated leg
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Climb {
+	/// Height gained per distance flown over the ground
+	Gradient(Ratio),
+	/// Vertical speed flown at a ground speed
+	Rate {
+		vertical_speed: Velocity,
+		ground_speed: Velocity,
+	},
+}
+
+/// Where a leg terminates, and the distance flown along the leg to get there
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LegTermination {
+	pub point: Coordinates,
+	pub distance: Length,
+}
+
+impl LegCourse {
+	/// Returns the true track made good over the ground
+	pub fn track(&self) -> Angle {
+		match *self {
+			LegCourse::Course(course) => course,
+			LegCourse::Heading {
+				heading,
+				true_airspeed,
+				wind,
+			} => wind.track_for_heading(heading, true_airspeed).0,
+		}
+	}
+}
+
+impl Coordinates {
+	/// Returns where a leg flown from `self` on `course` intercepts the `next` leg (ARINC 424 CI and VI legs). Heading
+	/// legs are flown on the constant track made good through the wind.
+	///
+	/// A great circle leg may be intercepted anywhere before its end, and an arc leg anywhere along the arc. Returns
+	/// `None` if the leg never intercepts the next leg ahead of `self`
+	pub fn intercept_termination(self, course: LegCourse, next: &Segment) -> Option<LegTermination> {
+		let track = course.track();
+		let point = match next {
+			Segment::GreatCircle(next) => self.intercept_great_circle(track, next),
+			Segment::Arc(next) => self.intercept_arc(track, next),
+		}?;
+
+		Some(LegTermination {
+			point,
+			distance: self.distance_to(point),
+		})
+	}
+
+	/// Returns where a leg flown from `self` on `course` first reaches `distance` from `navaid` (ARINC 424 CD, FD and
+	/// VD legs).
+	///
+	/// Returns `None` if the leg never reaches that distance ahead of `self`
+	pub fn dme_termination(self, course: LegCourse, navaid: Coordinates, distance: Length) -> Option<LegTermination> {
+		let track = course.track();
+		let (first, second) = navaid.small_circle_great_circle_intersection(distance, self, track)?;
+
+		self.nearest_ahead(track, [first, second].into_iter())
+	}
+
+	/// Returns where a leg flown from `self` on `course` crosses the magnetic `radial` from `navaid`, which has
+	/// `station_declination` (positive east) (ARINC 424 CR and VR legs). Only the radial itself terminates the leg, not
+	/// its reciprocal on the other side of the navaid.
+	///
+	/// Returns `None` if the leg never crosses the radial ahead of `self`
+	pub fn radial_termination(
+		self, course: LegCourse, navaid: Coordinates, radial: Angle, station_declination: Angle,
+	) -> Option<LegTermination> {
+		let track = course.track();
+		let radial = radial + station_declination;
+		let (first, second) = self.place_bearing_intersection(track, navaid, radial);
+		if first.lat.value.is_nan() || first.long.value.is_nan() {
+			return None;
+		}
+
+		self.nearest_ahead(
+			track,
+			[first, second].into_iter().filter(|point| {
+				navaid.distance_to(*point) < POSITION_TOLERANCE
+					|| diff_angle(radial, navaid.bearing_to(*point), Direction::Either).abs() < Angle::HALF_TURN / 2.0
+			}),
+		)
+	}
+
+	/// Returns where a leg flown from `self` on `course` reaches `target_altitude`, climbing from `altitude` by `climb`
+	/// (ARINC 424 CA, VA and FA legs). Wind is accounted for by the track of a heading leg, and by the ground speed of
+	/// a climb rate. A leg which starts at or above `target_altitude` terminates at `self`.
+	///
+	/// Returns `None` if the aircraft is not climbing
+	pub fn altitude_termination(
+		self, course: LegCourse, altitude: Length, target_altitude: Length, climb: Climb,
+	) -> Option<LegTermination> {
+		let height = target_altitude - altitude;
+		if height <= Length::ZERO {
+			return Some(LegTermination {
+				point: self,
+				distance: Length::ZERO,
+			});
+		}
+
+		let gradient = match climb {
+			Climb::Gradient(gradient) => gradient,
+			Climb::Rate {
+				vertical_speed,
+				ground_speed,
+			} => vertical_speed / ground_speed,
+		};
+		if gradient.value <= 0.0 || !gradient.value.is_finite() {
+			return None;
+		}
+
+		let distance = height / gradient;
+		Some(LegTermination {
+			point: self.bearing_distance(course.track(), distance),
+			distance,
+		})
+	}
+
+	fn nearest_ahead(self, track: Angle, points: impl Iterator<Item = Coordinates>) -> Option<LegTermination> {
+		points
+			.filter(|point| self.is_ahead(track, *point))
+			.map(|point| LegTermination {
+				point,
+				distance: self.distance_to(point),
+			})
+			.min_by(|a, b| a.distance.value.total_cmp(&b.distance.value))
+	}
+
+	fn intercept_great_circle(self, track: Angle, next: &GreatCircleSegment) -> Option<Coordinates> {
+		let course = next.start_course();
+		let point = if self.cross_track_distance(next.start, course).abs() < POSITION_TOLERANCE {
+			// Already on the great circle of the next leg
+			self
+		} else {
+			let point = self.place_bearing_intersection(track, next.start, course).0;
+			if point.lat.value.is_nan() || point.long.value.is_nan() || !self.is_ahead(track, point) {
+				// The courses are parallel, or they diverge
+				return None;
+			}
+			point
+		};
+
+		if point.along_track_distance(next.start, course) > next.length() + POSITION_TOLERANCE {
+			return None;
+		}
+
+		Some(point)
+	}
+
+	fn intercept_arc(self, track: Angle, next: &ArcSegment) -> Option<Coordinates> {
+		let (first, second) = next
+			.centre
+			.small_circle_great_circle_intersection(next.radius, self, track)?;
+		let length = next.length();
+
+		[first, second]
+			.into_iter()
+			.filter(|point| self.is_ahead(track, *point))
+			.filter(|point| {
+				let along = next.along_track_distance(*point);
+				along >= -POSITION_TOLERANCE && along <= length + POSITION_TOLERANCE
+			})
+			.min_by(|a, b| self.distance_to(*a).value.total_cmp(&self.distance_to(*b).value))
+	}
+
+	fn is_ahead(self, track: Angle, point: Coordinates) -> bool {
+		self.distance_to(point) < POSITION_TOLERANCE
+			|| diff_angle(track, self.bearing_to(point), Direction::Either).abs() < Angle::HALF_TURN / 2.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{
+		angle::{degree, radian},
+		length::{foot, nautical_mile},
+		ratio::ratio,
+		velocity::{foot_per_minute, knot},
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_intercept_great_circle() {
+		let start = Coordinates::new(0.0, 0.0);
+		let fix = Coordinates::new(1.0, 1.0);
+		let next: Segment = GreatCircleSegment::new(Coordinates::new(0.0, 1.0), fix).into();
+
+		let termination = start
+			.intercept_termination(LegCourse::Course(Angle::new::<degree>(90.0)), &next)
+			.unwrap();
+		assert_uom_eq!(termination.point.lat, Angle::ZERO, 1e-9);
+		assert_uom_eq!(termination.point.long, Angle::new::<degree>(1.0), 1e-9);
+		assert_uom_eq!(
+			termination.distance,
+			start.distance_to(Coordinates::new(0.0, 1.0)),
+			1e-3
+		);
+
+		// Diverging, and meeting the next leg beyond its end
+		for course in [270.0, 0.0, 30.0] {
+			assert!(start
+				.intercept_termination(LegCourse::Course(Angle::new::<degree>(course)), &next)
+				.is_none());
+		}
+
+		// Already on the great circle of the next leg, but beyond its end
+		let beyond = GreatCircleSegment::new(Coordinates::new(0.0, 1.0), fix).point_at(next.length() * 1.5);
+		assert!(beyond
+			.intercept_termination(LegCourse::Course(Angle::new::<degree>(90.0)), &next)
+			.is_none());
+
+		// Already on the next leg itself
+		let on_leg = next.point_at(next.length() / 2.0);
+		let termination = on_leg
+			.intercept_termination(LegCourse::Course(Angle::new::<degree>(90.0)), &next)
+			.unwrap();
+		assert_eq!(termination.point, on_leg);
+		assert_eq!(termination.distance, Length::ZERO);
+	}
+
+	#[test]
+	fn test_intercept_heading() {
+		let start = Coordinates::new(0.0, 0.0);
+		let next: Segment = GreatCircleSegment::new(Coordinates::new(-1.0, 1.0), Coordinates::new(1.0, 1.0)).into();
+		let wind = Wind {
+			direction: Angle::new::<degree>(180.0),
+			speed: Velocity::new::<knot>(50.0),
+		};
+		let course = LegCourse::Heading {
+			heading: Angle::new::<degree>(90.0),
+			true_airspeed: Velocity::new::<knot>(250.0),
+			wind,
+		};
+
+		let termination = start.intercept_termination(course, &next).unwrap();
+		let drift = Angle::new::<radian>(0.2f64.atan());
+		assert_uom_eq!(course.track(), Angle::new::<degree>(90.0) - drift, 1e-9);
+		assert!(termination.point.lat > Angle::ZERO);
+		assert_uom_eq!(termination.point.long, Angle::new::<degree>(1.0), 1e-9);
+	}
+
+	#[test]
+	fn test_intercept_arc() {
+		let centre = Coordinates::new(40.0, -75.0);
+		let radius = Length::new::<nautical_mile>(10.0);
+		let next: Segment = ArcSegment {
+			centre,
+			radius,
+			start: centre.bearing_distance(Angle::new::<degree>(180.0), radius),
+			end: centre.bearing_distance(Angle::ZERO, radius),
+			direction: Direction::Right,
+		}
+		.into();
+
+		let start = centre.bearing_distance(Angle::new::<degree>(270.0), Length::new::<nautical_mile>(20.0));
+		let termination = start
+			.intercept_termination(LegCourse::Course(start.bearing_to(centre)), &next)
+			.unwrap();
+		assert_uom_eq!(centre.distance_to(termination.point), radius, 0.1);
+		assert_uom_eq!(termination.distance, Length::new::<nautical_mile>(10.0), 0.1);
+
+		// The circle is crossed ahead, but only on the side the arc does not cover
+		let start = centre.bearing_distance(Angle::new::<degree>(90.0), Length::new::<nautical_mile>(5.0));
+		let course = start.bearing_to(centre) + Angle::HALF_TURN;
+		assert!(start.intercept_termination(LegCourse::Course(course), &next).is_none());
+	}
+
+	#[test]
+	fn test_dme_termination() {
+		let navaid = Coordinates::new(10.0, 20.0);
+		let distance = Length::new::<nautical_mile>(15.0);
+		let start = navaid.bearing_distance(Angle::new::<degree>(200.0), Length::new::<nautical_mile>(5.0));
+		let course = LegCourse::Course(Angle::new::<degree>(200.0));
+
+		let termination = start.dme_termination(course, navaid, distance).unwrap();
+		assert_uom_eq!(navaid.distance_to(termination.point), distance, 0.1);
+		assert_uom_eq!(termination.distance, Length::new::<nautical_mile>(10.0), 0.1);
+
+		// Crossing the near side of the circle first
+		let start = navaid.bearing_distance(Angle::new::<degree>(20.0), Length::new::<nautical_mile>(30.0));
+		let termination = start.dme_termination(course, navaid, distance).unwrap();
+		assert_uom_eq!(termination.distance, Length::new::<nautical_mile>(15.0), 0.1);
+
+		// Flying away from the circle
+		let course = LegCourse::Course(Angle::new::<degree>(20.0));
+		assert!(start.dme_termination(course, navaid, distance).is_none());
+	}
+
+	#[test]
+	fn test_radial_termination() {
+		let navaid = Coordinates::new(0.0, 0.0);
+		let declination = Angle::new::<degree>(-10.0);
+		let start = Coordinates::new(1.0, -1.0);
+
+		// The 280 magnetic radial is the 270 true radial
+		let termination = start
+			.radial_termination(
+				LegCourse::Course(Angle::new::<degree>(180.0)),
+				navaid,
+				Angle::new::<degree>(280.0),
+				declination,
+			)
+			.unwrap();
+		assert_uom_eq!(termination.point.lat, Angle::ZERO, 1e-9);
+		assert_uom_eq!(termination.point.long, Angle::new::<degree>(-1.0), 1e-9);
+
+		// Crossing the reciprocal of the radial does not terminate the leg
+		assert!(start
+			.radial_termination(
+				LegCourse::Course(Angle::new::<degree>(180.0)),
+				navaid,
+				Angle::new::<degree>(100.0),
+				declination,
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn test_altitude_termination() {
+		let start = Coordinates::new(47.4, 8.5);
+		let course = LegCourse::Course(Angle::new::<degree>(280.0));
+		let altitude = Length::new::<foot>(1400.0);
+
+		let termination = start
+			.altitude_termination(
+				course,
+				altitude,
+				Length::new::<foot>(3400.0),
+				Climb::Rate {
+					vertical_speed: Velocity::new::<foot_per_minute>(1000.0),
+					ground_speed: Velocity::new::<knot>(120.0),
+				},
+			)
+			.unwrap();
+		assert_uom_eq!(termination.distance, Length::new::<nautical_mile>(4.0), 1e-6);
+		assert_uom_eq!(start.distance_to(termination.point), termination.distance, 1e-3);
+		assert_uom_eq!(start.bearing_to(termination.point), Angle::new::<degree>(280.0), 1e-9);
+
+		let gradient = Climb::Gradient(Ratio::new::<ratio>(0.05));
+		let termination = start
+			.altitude_termination(course, altitude, Length::new::<foot>(2400.0), gradient)
+			.unwrap();
+		assert_uom_eq!(termination.distance, Length::new::<foot>(20000.0), 1e-6);
+
+		let termination = start
+			.altitude_termination(course, altitude, Length::new::<foot>(1000.0), gradient)
+			.unwrap();
+		assert_eq!(termination.point, start);
+		assert_eq!(termination.distance, Length::ZERO);
+
+		assert!(start
+			.altitude_termination(
+				course,
+				altitude,
+				Length::new::<foot>(2400.0),
+				Climb::Gradient(Ratio::ZERO)
+			)
+			.is_none());
+	}
+}
@@ -0,0 +1,489 @@
+use uom::{
+	si::f64::{Angle, Length, Velocity},
+	ConstZero,
+};
+
+use crate::{
+	constants::EARTH_RADIUS,
+	kinematics::turn_radius,
+	segment::{quarter_turn, ArcSegment, GreatCircleSegment},
+	utility::diff_angle,
+	Coordinates,
+	Direction,
+};
+
+/// A turn which joins two great circle courses meeting at a waypoint, without flying over the waypoint itself
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlyByTurn {
+	/// The turn itself, starting tangent to the inbound course and ending tangent to the outbound course
+	pub arc: ArcSegment,
+	/// The change in track over the turn - positive when turning right, or negative when turning left
+	pub track_change: Angle,
+	/// The distance before the waypoint, along the inbound course, at which the turn begins. The turn ends the same
+	/// distance after the waypoint, along the outbound course
+	pub anticipation_distance: Length,
+}
+
+/// A turn onto an intercept course, followed by a great circle segment along that course to the point where it joins a
+/// leg
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InterceptTransition {
+	/// The turn from the initial course onto the intercept course
+	pub turn: ArcSegment,
+	/// The intercept course, from the end of the turn to the join point on the leg
+	pub intercept: GreatCircleSegment,
+}
+
+/// Resolves `Direction::Either` to the shorter turn from `course` to `new_course`
+fn shorter_turn_direction(course: Angle, new_course: Angle, direction: Direction) -> Direction {
+	match direction {
+		Direction::Either if diff_angle(course, new_course, Direction::Either) < Angle::ZERO => Direction::Left,
+		Direction::Either => Direction::Right,
+		direction => direction,
+	}
+}
+
+/// Returns the distance from a waypoint to either end of the fly-by turn at `radius` through `track_change`. Returns
+/// `None` if the turn would need to begin more than a quarter of the way around the planet from the waypoint
+pub(crate) fn anticipation_distance(radius: Length, track_change: Angle) -> Option<Length> {
+	let radial_distance: Angle = (radius / EARTH_RADIUS).into();
+	let anticipation = radial_distance.tan() * (track_change.abs() / 2.0).tan();
+	if anticipation.value > 1.0 {
+		return None;
+	}
+
+	Some(EARTH_RADIUS * anticipation.asin().value)
+}
+
+/// Returns the point where the turn circle at `radius` around `centre` first reaches the leg passing through
+/// `leg_reference` on `leg_course`, when turning onto a course to intercept the leg. This is the crossing furthest
+/// along the leg, as the turn only reaches the leg when its centre lies between the leg and the point where the turn is
+/// parallel to the leg. Returns `None` if the circle does not cross the leg
+fn turn_leg_crossing(
+	centre: Coordinates, radius: Length, leg_reference: Coordinates, leg_course: Angle,
+) -> Option<Coordinates> {
+	// Measured from the point of the leg abeam the centre, which lies inside the circle whenever the circle crosses
+	let along = centre.along_track_distance(leg_reference, leg_course);
+	let abeam = leg_reference.bearing_distance(leg_course, along);
+	let abeam_course = abeam.bearing_to(leg_reference.bearing_distance(leg_course, along + radius));
+
+	centre.first_small_circle_intersection(radius, abeam, abeam_course)
+}
+
+impl Coordinates {
+	/// Returns the fly-by turn at `radius` at waypoint `self`, from `inbound_course` (the course arriving at `self`)
+	/// to `outbound_course` (the course departing `self`). When `direction` is `Either`, the shorter turn is chosen.
+	///
+	/// Returns `None` if the turn is 180&deg; or more, or if the turn would need to begin more than a quarter of the
+	/// way around the planet from the waypoint
+	pub fn fly_by_turn(
+		self, inbound_course: Angle, outbound_course: Angle, radius: Length, direction: Direction,
+	) -> Option<FlyByTurn> {
+		let direction = shorter_turn_direction(inbound_course, outbound_course, direction);
+
+		let track_change = diff_angle(inbound_course, outbound_course, direction);
+		if track_change.abs() >= Angle::HALF_TURN {
+			return None;
+		}
+
+		let anticipation_distance = anticipation_distance(radius, track_change)?;
+
+		let entry = self.bearing_distance(inbound_course + Angle::HALF_TURN, anticipation_distance);
+		let exit = self.bearing_distance(outbound_course, anticipation_distance);
+
+		let entry_course = if anticipation_distance > Length::ZERO {
+			entry.bearing_to(self)
+		} else {
+			inbound_course
+		};
+		let centre_bearing = entry_course + quarter_turn(direction);
+
+		Some(FlyByTurn {
+			arc: ArcSegment {
+				centre: entry.bearing_distance(centre_bearing, radius),
+				radius,
+				start: entry,
+				end: exit,
+				direction,
+			},
+			track_change,
+			anticipation_distance,
+		})
+	}
+
+	/// Returns the fly-by turn at waypoint `self` flown at `ground_speed` with the bank limited to `max_bank`. See
+	/// `fly_by_turn` for the details of the construction
+	pub fn fly_by_turn_for_speed(
+		self, inbound_course: Angle, outbound_course: Angle, ground_speed: Velocity, max_bank: Angle,
+		direction: Direction,
+	) -> Option<FlyByTurn> {
+		self.fly_by_turn(
+			inbound_course,
+			outbound_course,
+			turn_radius(ground_speed, max_bank),
+			direction,
+		)
+	}
+
+	/// Returns the turn at `radius` in `direction` from `self` on `course` onto `new_course`. When `direction` is
+	/// `Either`, the shorter turn is chosen. When the courses are the same, the turn has no length
+	pub fn turn_to_course(self, course: Angle, new_course: Angle, radius: Length, direction: Direction) -> ArcSegment {
+		let direction = shorter_turn_direction(course, new_course, direction);
+		let track_change = diff_angle(course, new_course, direction);
+
+		let centre = self.bearing_distance(course + quarter_turn(direction), radius);
+
+		// Turned from the radial through the start, as bearings measured at the centre are skewed from courses measured
+		// at the aircraft by the convergence of the meridians
+		let end = if track_change == Angle::ZERO {
+			self
+		} else {
+			centre.bearing_distance(centre.bearing_to(self) + track_change, radius)
+		};
+
+		ArcSegment {
+			centre,
+			radius,
+			start: self,
+			end,
+			direction,
+		}
+	}
+
+	/// Returns the transition after flying over waypoint `self` on `inbound_course`, which turns at `radius` in
+	/// `direction` and then intercepts the great circle leaving `self` on `outbound_course` at `intercept_angle`. See
+	/// `turn_to_intercept` for the details of the construction
+	pub fn fly_over_transition(
+		self, inbound_course: Angle, outbound_course: Angle, radius: Length, direction: Direction,
+		intercept_angle: Angle,
+	) -> Option<InterceptTransition> {
+		self.turn_to_intercept(
+			inbound_course,
+			radius,
+			direction,
+			self,
+			outbound_course,
+			intercept_angle,
+		)
+	}
+
+	/// Returns the transition from `self` on `course`, which turns at `radius` in `direction` onto an intercept course,
+	/// and then follows that course until it joins the leg passing through `leg_reference` on `leg_course`. When
+	/// `direction` is `Either`, the shorter turn onto the leg course is chosen.
+	///
+	/// The intercept course meets the leg at `intercept_angle`, approaching from whichever side of the leg the aircraft
+	/// would be on after turning parallel to it. When the turn would reach the leg before the intercept course, the
+	/// intercept angle is reduced to half the track change at which the turn reaches the leg. If the aircraft is
+	/// already converging with the leg at less than the intercept angle, it does not turn at all.
+	///
+	/// Returns `None` if the intercept course never reaches the leg ahead of the aircraft
+	pub fn turn_to_intercept(
+		self, course: Angle, radius: Length, direction: Direction, leg_reference: Coordinates, leg_course: Angle,
+		intercept_angle: Angle,
+	) -> Option<InterceptTransition> {
+		let direction = shorter_turn_direction(course, leg_course, direction);
+
+		let turn_onto = |heading: Angle| self.turn_to_course(course, heading, radius, direction);
+		let centre = self.bearing_distance(course + quarter_turn(direction), radius);
+
+		// Left of the leg (negative) means the intercept course must point to the right of the leg course
+		let parallel = turn_onto(leg_course)
+			.end
+			.cross_track_distance(leg_reference, leg_course);
+		let side = if parallel < Length::ZERO { 1.0 } else { -1.0 };
+		let parallel_turn = diff_angle(course, leg_course, direction).abs();
+
+		if parallel.abs() < radius / 1e3 {
+			// Turning onto the leg course puts the aircraft on the leg itself, which needs no turn at all when the
+			// aircraft is already on the leg course
+			let turn = turn_onto(leg_course);
+			return Some(InterceptTransition {
+				turn,
+				intercept: GreatCircleSegment::new(turn.end, turn.end),
+			});
+		}
+
+		let mut heading = leg_course + intercept_angle * side;
+		if diff_angle(course, heading, direction).abs() > parallel_turn + Angle::HALF_TURN {
+			// Turning onto the intercept course would take the long way round, as the aircraft is already converging
+			// with the leg at a shallower angle
+			heading = course;
+		} else if let Some(crossing) = turn_leg_crossing(centre, radius, leg_reference, leg_course) {
+			// The course at the crossing, turned through the same angle around the centre as `turn_to_course`
+			let crossing_course = course + diff_angle(centre.bearing_to(self), centre.bearing_to(crossing), direction);
+			let crossing_angle = diff_angle(leg_course, crossing_course, Direction::Either).abs();
+			if crossing_angle <= intercept_angle {
+				heading = leg_course + crossing_angle / 2.0 * side;
+			}
+		}
+
+		let turn = turn_onto(heading);
+		let join = turn
+			.end
+			.place_bearing_intersection(heading, leg_reference, leg_course)
+			.0;
+		if diff_angle(heading, turn.end.bearing_to(join), Direction::Either).abs() >= Angle::HALF_TURN / 2.0 {
+			return None;
+		}
+
+		Some(InterceptTransition {
+			turn,
+			intercept: GreatCircleSegment::new(turn.end, join),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	use uom::{
+		si::{angle::degree, length::nautical_mile, velocity::knot},
+		ConstZero,
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_fly_by_turn() {
+		let waypoint = Coordinates::new(0.0, 0.0);
+		let radius = Length::new::<nautical_mile>(2.0);
+
+		let turn = waypoint
+			.fly_by_turn(Angle::ZERO, Angle::new::<degree>(90.0), radius, Direction::Either)
+			.unwrap();
+
+		assert_eq!(turn.arc.direction, Direction::Right);
+		assert_uom_eq!(turn.track_change, Angle::new::<degree>(90.0));
+		assert_uom_eq!(turn.anticipation_distance, radius, 1e-1);
+		assert_uom_eq!(turn.arc.length(), radius * PI / 2.0, 1e-1);
+		assert_uom_eq!(turn.arc.centre.distance_to(turn.arc.start), radius, 1e-3);
+		assert_uom_eq!(turn.arc.centre.distance_to(turn.arc.end), radius, 1e-3);
+		assert_uom_eq!(turn.arc.centre.distance_to(waypoint), radius * 2.0f64.sqrt(), 1e-1);
+		assert_uom_eq!(turn.arc.start_course(), Angle::ZERO, 1e-6);
+		assert_uom_eq!(turn.arc.end_course(), Angle::new::<degree>(90.0), 1e-6);
+	}
+
+	#[test]
+	fn test_fly_by_turn_left() {
+		let waypoint = Coordinates::new(45.0, 179.9);
+		let radius = Length::new::<nautical_mile>(3.0);
+
+		let turn = waypoint
+			.fly_by_turn(
+				Angle::new::<degree>(100.0),
+				Angle::new::<degree>(40.0),
+				radius,
+				Direction::Either,
+			)
+			.unwrap();
+
+		assert_eq!(turn.arc.direction, Direction::Left);
+		assert_uom_eq!(turn.track_change, Angle::new::<degree>(-60.0));
+		assert_uom_eq!(turn.anticipation_distance, radius * (PI / 6.0).tan(), 1e-1);
+		assert_uom_eq!(turn.arc.sweep(), Angle::new::<degree>(-60.0), 1e-6);
+		assert_uom_eq!(turn.arc.centre.distance_to(turn.arc.end), radius, 1e-3);
+
+		assert!(waypoint
+			.fly_by_turn(
+				Angle::new::<degree>(100.0),
+				Angle::new::<degree>(40.0),
+				radius,
+				Direction::Right,
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn test_fly_by_turn_for_speed() {
+		let waypoint = Coordinates::new(-33.9, 151.2);
+		let inbound = Angle::new::<degree>(340.0);
+		let outbound = Angle::new::<degree>(40.0);
+
+		let turn = waypoint
+			.fly_by_turn_for_speed(
+				inbound,
+				outbound,
+				Velocity::new::<knot>(250.0),
+				Angle::new::<degree>(25.0),
+				Direction::Either,
+			)
+			.unwrap();
+		assert_eq!(turn.arc.direction, Direction::Right);
+		assert_uom_eq!(turn.arc.radius, Length::new::<nautical_mile>(1.953), 5.0);
+		assert_eq!(
+			Some(turn),
+			waypoint.fly_by_turn(inbound, outbound, turn.arc.radius, Direction::Either)
+		);
+
+		// Slower, the same bank gives a tighter turn which begins closer to the waypoint
+		let slower = waypoint
+			.fly_by_turn_for_speed(
+				inbound,
+				outbound,
+				Velocity::new::<knot>(180.0),
+				Angle::new::<degree>(25.0),
+				Direction::Either,
+			)
+			.unwrap();
+		assert!(slower.anticipation_distance < turn.anticipation_distance);
+	}
+
+	#[test]
+	fn test_turn_to_course() {
+		let start = Coordinates::new(40.0, -75.0);
+		let radius = Length::new::<nautical_mile>(2.0);
+
+		for course in [Angle::ZERO, Angle::new::<degree>(90.0), Angle::new::<degree>(235.0)] {
+			let turn = start.turn_to_course(course, course, radius, Direction::Right);
+			assert_eq!(turn.end, start);
+			assert_eq!(turn.sweep(), Angle::ZERO);
+			assert_eq!(turn.length(), Length::ZERO);
+
+			let turn = start.turn_to_course(course, course + Angle::new::<degree>(1.0), radius, Direction::Right);
+			assert_uom_eq!(turn.sweep(), Angle::new::<degree>(1.0), 1e-9);
+			assert_uom_eq!(turn.length(), radius * PI / 180.0, 1e-3);
+
+			let turn = start.turn_to_course(course, course - Angle::new::<degree>(90.0), radius, Direction::Left);
+			assert_uom_eq!(turn.sweep(), Angle::new::<degree>(-90.0), 1e-9);
+			assert_uom_eq!(
+				diff_angle(
+					turn.end_course(),
+					course - Angle::new::<degree>(90.0),
+					Direction::Either
+				),
+				Angle::ZERO,
+				Angle::new::<degree>(0.05).value
+			);
+		}
+	}
+
+	#[test]
+	fn test_fly_over_transition() {
+		let waypoint = Coordinates::new(0.0, 0.0);
+		let radius = Length::new::<nautical_mile>(2.0);
+
+		let transition = waypoint
+			.fly_over_transition(
+				Angle::ZERO,
+				Angle::new::<degree>(90.0),
+				radius,
+				Direction::Right,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+
+		assert_eq!(transition.turn.start, waypoint);
+		assert_uom_eq!(transition.turn.sweep(), Angle::new::<degree>(135.0), 1e-6);
+		assert_uom_eq!(transition.intercept.start_course(), Angle::new::<degree>(135.0), 1e-6);
+		assert_uom_eq!(transition.intercept.end.lat, Angle::ZERO, 1e-9);
+		assert!(transition.intercept.end.long > transition.turn.end.long);
+	}
+
+	#[test]
+	fn test_fly_over_transition_small_turn() {
+		let waypoint = Coordinates::new(20.0, 179.95);
+		let radius = Length::new::<nautical_mile>(2.0);
+		let outbound = Angle::new::<degree>(350.0);
+
+		let transition = waypoint
+			.fly_over_transition(
+				Angle::ZERO,
+				outbound,
+				radius,
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+
+		assert_eq!(transition.turn.direction, Direction::Left);
+		let sweep = transition.turn.sweep();
+		assert!(sweep < Angle::new::<degree>(-10.0) && sweep > Angle::new::<degree>(-55.0));
+
+		let join = transition.intercept.end;
+		assert_uom_eq!(join.cross_track_distance(waypoint, outbound), Length::ZERO, 1e-3);
+		assert!(join.along_track_distance(waypoint, outbound) > Length::ZERO);
+		assert!(transition.turn.end.cross_track_distance(waypoint, outbound) > Length::ZERO);
+	}
+
+	#[test]
+	fn test_fly_over_transition_straight_ahead() {
+		let waypoint = Coordinates::new(40.0, -75.0);
+		let course = Angle::new::<degree>(60.0);
+
+		let transition = waypoint
+			.fly_over_transition(
+				course,
+				course,
+				Length::new::<nautical_mile>(2.0),
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+		assert_eq!(transition.turn.end, waypoint);
+		assert_eq!(transition.turn.length(), Length::ZERO);
+		assert_eq!(transition.intercept.length(), Length::ZERO);
+	}
+
+	#[test]
+	fn test_turn_to_intercept_reduced() {
+		let waypoint = Coordinates::new(40.0, -75.0);
+		let radius = Length::new::<nautical_mile>(3.0);
+		let outbound = Angle::new::<degree>(20.0);
+
+		// The turn reaches the outbound leg before turning through 45 degrees onto the intercept
+		let transition = waypoint
+			.fly_over_transition(
+				Angle::ZERO,
+				outbound,
+				radius,
+				Direction::Either,
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+		assert_eq!(transition.turn.direction, Direction::Right);
+		assert!(transition.turn.end.cross_track_distance(waypoint, outbound) < Length::ZERO);
+
+		let intercept_angle = transition.intercept.start_course() - outbound;
+		assert!(intercept_angle > Angle::ZERO && intercept_angle < Angle::new::<degree>(45.0));
+		let crossing = waypoint
+			.turn_to_course(Angle::ZERO, outbound + intercept_angle * 2.0, radius, Direction::Right)
+			.end;
+		assert_uom_eq!(crossing.cross_track_distance(waypoint, outbound), Length::ZERO, 1e-3);
+
+		let join = transition.intercept.end;
+		assert_uom_eq!(join.cross_track_distance(waypoint, outbound), Length::ZERO, 1e-3);
+		assert!(join.along_track_distance(waypoint, outbound) > Length::ZERO);
+	}
+
+	#[test]
+	fn test_turn_to_intercept_converging() {
+		let start = Coordinates::new(0.0, 0.0);
+		let leg_reference = Coordinates::new(1.0, 0.0);
+
+		let transition = start
+			.turn_to_intercept(
+				Angle::new::<degree>(10.0),
+				Length::new::<nautical_mile>(2.0),
+				Direction::Either,
+				leg_reference,
+				Angle::new::<degree>(90.0),
+				Angle::new::<degree>(45.0),
+			)
+			.unwrap();
+
+		assert_eq!(transition.turn.direction, Direction::Right);
+		assert_uom_eq!(transition.intercept.start_course(), Angle::new::<degree>(45.0), 1e-6);
+
+		let transition = start.turn_to_intercept(
+			Angle::new::<degree>(200.0),
+			Length::new::<nautical_mile>(2.0),
+			Direction::Left,
+			leg_reference,
+			Angle::new::<degree>(90.0),
+			Angle::new::<degree>(30.0),
+		);
+		assert!(transition.is_some());
+	}
+}
@@ -0,0 +1,343 @@
+use std::marker::PhantomData;
+
+use uom::{
+	si::f64::{Angle, Length},
+	ConstZero,
+};
+
+use crate::{
+	path::Path,
+	pseudo_waypoint::{PathDistance, PseudoWaypoint},
+	Coordinates,
+};
+
+/// Largest distance of an altitude outside a constraint for it to still be treated as meeting the constraint, so that
+/// altitudes computed along the path meet the constraints they were built from
+const ALTITUDE_TOLERANCE: Length = Length {
+	dimension: PhantomData,
+	units: PhantomData,
+	value: 1e-3,
+};
+
+/// A restriction on the altitude at which a point is crossed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AltitudeConstraint {
+	At(Length),
+	AtOrAbove(Length),
+	AtOrBelow(Length),
+	Window { lower: Length, upper: Length },
+}
+
+impl AltitudeConstraint {
+	/// Returns whether crossing at `altitude` meets the constraint
+	pub fn contains(&self, altitude: Length) -> bool { (self.clamp(altitude) - altitude).abs() <= ALTITUDE_TOLERANCE }
+
+	/// Returns the altitude meeting the constraint which is closest to `altitude`
+	pub fn clamp(&self, altitude: Length) -> Length {
+		match *self {
+			AltitudeConstraint::At(at) => at,
+			AltitudeConstraint::AtOrAbove(lower) => altitude.max(lower),
+			AltitudeConstraint::AtOrBelow(upper) => altitude.min(upper),
+			AltitudeConstraint::Window { lower, upper } => altitude.max(lower).min(upper),
+		}
+	}
+
+	/// Returns the lowest altitude meeting the constraint, or its upper limit when it has no lower one
+	fn lowest(&self) -> Length {
+		match *self {
+			AltitudeConstraint::At(altitude)
+			| AltitudeConstraint::AtOrAbove(altitude)
+			| AltitudeConstraint::AtOrBelow(altitude)
+			| AltitudeConstraint::Window { lower: altitude, .. } => altitude,
+		}
+	}
+}
+
+/// A point along a lateral path at which the vertical path is constrained
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VerticalConstraint {
+	/// Distance along the lateral path
+	pub distance: Length,
+	pub altitude: Option<AltitudeConstraint>,
+	/// Descent angle of the path leading to the point, positive downwards, or `None` to use the default angle
+	pub flight_path_angle: Option<Angle>,
+}
+
+/// The altitude of a vertical path at a distance along the lateral path
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProfilePoint {
+	pub distance: Length,
+	pub altitude: Length,
+}
+
+/// A geometric descent path, made of straight lines in altitude against distance along the lateral path, from the top
+/// of descent at the cruise altitude to the last constraint
+#[derive(Clone, Debug, PartialEq)]
+pub struct DescentPath {
+	/// Ordered by distance, starting with the top of descent
+	points: Vec<ProfilePoint>,
+}
+
+impl DescentPath {
+	/// Returns the descent path from `cruise_altitude` through `constraints`, which must be ordered by distance and end
+	/// with an altitude constraint.
+	///
+	/// The path is built backwards from the lowest altitude meeting the last constraint. Each leg descends towards the
+	/// following constraint at its flight path angle, or `default_angle` if it has none, unless that would break the
+	/// altitude constraint at its start, in which case the leg joins the constraint instead. The top of descent is
+	/// where the path climbing back reaches `cruise_altitude`, and may lie before the start of the lateral path.
+	///
+	/// Returns `None` if the constraints are not ordered, a flight path angle is not a descent, or the last constraint
+	/// is not below the cruise altitude
+	pub fn new(constraints: &[VerticalConstraint], cruise_altitude: Length, default_angle: Angle) -> Option<Self> {
+		let last = constraints.last()?;
+		let mut next = ProfilePoint {
+			distance: last.distance,
+			altitude: last.altitude?.lowest(),
+		};
+		if next.altitude >= cruise_altitude {
+			return None;
+		}
+
+		let mut points = vec![next];
+		for pair in constraints.windows(2).rev() {
+			let (constraint, following) = (pair[0], pair[1]);
+			let angle = following.flight_path_angle.unwrap_or(default_angle);
+			if constraint.distance > following.distance || angle <= Angle::ZERO {
+				return None;
+			}
+
+			let projected = next.altitude + (next.distance - constraint.distance) * angle.tan();
+			let point = ProfilePoint {
+				distance: constraint.distance,
+				altitude: constraint
+					.altitude
+					.map_or(projected, |altitude| altitude.clamp(projected)),
+			};
+
+			if point.altitude >= cruise_altitude {
+				let distance = next.distance
+					- (cruise_altitude - next.altitude) * (next.distance - point.distance)
+						/ (point.altitude - next.altitude);
+				points.push(ProfilePoint {
+					distance,
+					altitude: cruise_altitude,
+				});
+				points.reverse();
+				return Some(Self { points });
+			}
+
+			points.push(point);
+			next = point;
+		}
+
+		let angle = constraints[0].flight_path_angle.unwrap_or(default_angle);
+		if angle <= Angle::ZERO {
+			return None;
+		}
+		points.push(ProfilePoint {
+			distance: next.distance - (cruise_altitude - next.altitude) / angle.tan(),
+			altitude: cruise_altitude,
+		});
+		points.reverse();
+
+		Some(Self { points })
+	}
+
+	/// Returns the points where the path changes angle, starting with the top of descent
+	pub fn points(&self) -> &[ProfilePoint] { &self.points }
+
+	/// Returns the distance along the lateral path to the top of descent
+	pub fn top_of_descent(&self) -> Length { self.points[0].distance }
+
+	/// Returns the top of descent placed on `path`. Returns `None` if it lies beyond either end of the path
+	pub fn top_of_descent_waypoint(&self, path: &Path) -> Option<PseudoWaypoint> {
+		path.pseudo_waypoint(PathDistance::FromStart(self.top_of_descent()))
+	}
+
+	/// Returns the altitude of the path `distance` along the lateral path. The path is level at the cruise altitude
+	/// before the top of descent, and at the last altitude after the last constraint
+	pub fn altitude_at(&self, distance: Length) -> Length {
+		match self.leg_at(distance) {
+			Some((from, to)) => {
+				from.altitude
+					+ (to.altitude - from.altitude) * (distance - from.distance) / (to.distance - from.distance)
+			},
+			None if distance < self.top_of_descent() => self.points[0].altitude,
+			None => self.points[self.points.len() - 1].altitude,
+		}
+	}
+
+	/// Returns the descent angle of the path `distance` along the lateral path, positive downwards
+	pub fn flight_path_angle_at(&self, distance: Length) -> Angle {
+		self.leg_at(distance).map_or(Angle::ZERO, |(from, to)| {
+			((from.altitude - to.altitude) / (to.distance - from.distance)).atan()
+		})
+	}
+
+	/// Returns the height of an aircraft at `position` and `altitude` above the path, where `position` is projected
+	/// onto the lateral `path`. Returns `None` if `path` is empty
+	pub fn vertical_deviation(&self, path: &Path, position: Coordinates, altitude: Length) -> Option<Length> {
+		Some(altitude - self.altitude_at(path.distance_along(position)?))
+	}
+
+	/// Returns the points at either end of the leg of the path `distance` along the lateral path, or `None` if it lies
+	/// before the top of descent or after the last constraint
+	fn leg_at(&self, distance: Length) -> Option<(ProfilePoint, ProfilePoint)> {
+		self.points
+			.windows(2)
+			.find(|pair| distance >= pair[0].distance && distance < pair[1].distance)
+			.map(|pair| (pair[0], pair[1]))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{
+		angle::degree,
+		length::{foot, meter, nautical_mile},
+	};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	fn constraint(
+		distance: f64, altitude: Option<AltitudeConstraint>, flight_path_angle: Option<f64>,
+	) -> VerticalConstraint {
+		VerticalConstraint {
+			distance: Length::new::<nautical_mile>(distance),
+			altitude,
+			flight_path_angle: flight_path_angle.map(Angle::new::<degree>),
+		}
+	}
+
+	fn feet(altitude: f64) -> Length { Length::new::<foot>(altitude) }
+
+	#[test]
+	fn test_altitude_constraint() {
+		let window = AltitudeConstraint::Window {
+			lower: feet(5000.0),
+			upper: feet(7000.0),
+		};
+		assert!(window.contains(feet(6000.0)));
+		assert!(!window.contains(feet(8000.0)));
+		assert!(window.contains(feet(7000.0) + Length::new::<meter>(1e-6)));
+		assert!(AltitudeConstraint::At(feet(3000.0)).contains(feet(1000.0) * 3.0));
+		assert!(!AltitudeConstraint::At(feet(3000.0)).contains(feet(3001.0)));
+		assert_eq!(window.clamp(feet(8000.0)), feet(7000.0));
+		assert_eq!(window.clamp(feet(4000.0)), feet(5000.0));
+
+		assert_eq!(AltitudeConstraint::At(feet(3000.0)).clamp(feet(4000.0)), feet(3000.0));
+		assert_eq!(
+			AltitudeConstraint::AtOrAbove(feet(3000.0)).clamp(feet(4000.0)),
+			feet(4000.0)
+		);
+		assert_eq!(
+			AltitudeConstraint::AtOrBelow(feet(3000.0)).clamp(feet(4000.0)),
+			feet(3000.0)
+		);
+	}
+
+	#[test]
+	fn test_descent_path() {
+		let default_angle = Angle::new::<degree>(2.5);
+		let constraints = [
+			// Too high to meet at the default angle, so the path is shallower before it
+			constraint(100.0, Some(AltitudeConstraint::AtOrBelow(feet(15000.0))), None),
+			constraint(130.0, None, None),
+			// Met at the default angle, so the path passes through it unbroken
+			constraint(
+				150.0,
+				Some(AltitudeConstraint::Window {
+					lower: feet(3000.0),
+					upper: feet(10000.0),
+				}),
+				None,
+			),
+			constraint(160.0, Some(AltitudeConstraint::At(feet(1500.0))), Some(3.0)),
+		];
+		let path = DescentPath::new(&constraints, feet(35000.0), default_angle).unwrap();
+		let points = path.points();
+		assert_eq!(points.len(), 5);
+
+		// Final approach at 3 degrees
+		let approach_altitude = feet(1500.0) + Length::new::<nautical_mile>(10.0) * Angle::new::<degree>(3.0).tan();
+		assert_uom_eq!(points[3].altitude, approach_altitude, 1e-6);
+		assert_uom_eq!(
+			path.flight_path_angle_at(Length::new::<nautical_mile>(155.0)),
+			Angle::new::<degree>(3.0),
+			1e-9
+		);
+		assert_uom_eq!(
+			path.flight_path_angle_at(Length::new::<nautical_mile>(140.0)),
+			default_angle,
+			1e-9
+		);
+		assert!(path.flight_path_angle_at(Length::new::<nautical_mile>(120.0)) < default_angle);
+
+		assert_eq!(points[1].altitude, feet(15000.0));
+		let top_of_descent =
+			Length::new::<nautical_mile>(100.0) - (feet(35000.0) - feet(15000.0)) / default_angle.tan();
+		assert_uom_eq!(path.top_of_descent(), top_of_descent, 1e-6);
+
+		assert_eq!(path.altitude_at(Length::ZERO), feet(35000.0));
+		assert_eq!(path.altitude_at(Length::new::<nautical_mile>(200.0)), feet(1500.0));
+		assert_uom_eq!(
+			path.altitude_at(Length::new::<nautical_mile>(155.0)),
+			(approach_altitude + feet(1500.0)) / 2.0,
+			1e-6
+		);
+		assert_eq!(
+			path.flight_path_angle_at(Length::new::<nautical_mile>(170.0)),
+			Angle::ZERO
+		);
+
+		assert!(DescentPath::new(&constraints, feet(1000.0), default_angle).is_none());
+		assert!(DescentPath::new(&constraints[..2], feet(35000.0), default_angle).is_none());
+	}
+
+	#[test]
+	fn test_top_of_descent_within_constraints() {
+		// Cruising below the constraint, so the descent starts between the first constraints
+		let constraints = [
+			constraint(20.0, Some(AltitudeConstraint::AtOrAbove(feet(12000.0))), None),
+			constraint(80.0, Some(AltitudeConstraint::At(feet(3000.0))), None),
+		];
+		let path = DescentPath::new(&constraints, feet(10000.0), Angle::new::<degree>(3.0)).unwrap();
+		assert_eq!(path.points().len(), 2);
+		// Climbing at 3 degrees back from the last constraint until reaching the cruise altitude
+		assert_uom_eq!(
+			path.top_of_descent(),
+			Length::new::<nautical_mile>(80.0) - feet(7000.0) / Angle::new::<degree>(3.0).tan(),
+			1e-6
+		);
+	}
+
+	#[test]
+	fn test_vertical_deviation() {
+		let from = Coordinates::new(0.0, 0.0);
+		let to = Coordinates::new(0.0, 2.0);
+		let lateral = Path::from_waypoints(&[from, to]);
+		let constraints = [constraint(
+			lateral.length().get::<nautical_mile>(),
+			Some(AltitudeConstraint::At(feet(2000.0))),
+			None,
+		)];
+		let path = DescentPath::new(&constraints, feet(20000.0), Angle::new::<degree>(3.0)).unwrap();
+
+		let top_of_descent = path.top_of_descent_waypoint(&lateral).unwrap();
+		assert_uom_eq!(top_of_descent.distance, path.top_of_descent(), 1e-9);
+		assert_uom_eq!(top_of_descent.course, Angle::new::<degree>(90.0), 1e-9);
+
+		let position = lateral
+			.point_at(path.top_of_descent() + Length::new::<nautical_mile>(10.0))
+			.unwrap();
+		let on_path = path.altitude_at(path.top_of_descent() + Length::new::<nautical_mile>(10.0));
+		assert_uom_eq!(
+			path.vertical_deviation(&lateral, position, on_path + feet(500.0))
+				.unwrap(),
+			feet(500.0),
+			1e-3
+		);
+	}
+}
@@ -0,0 +1,202 @@
+use uom::{
+	si::f64::{Angle, Ratio, Velocity},
+	ConstZero,
+};
+
+use crate::{
+	utility::{clamp_angle_cw, diff_angle},
+	Direction,
+};
+
+/// The motion of the air mass relative to the ground
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Wind {
+	/// True direction the wind is blowing **from**
+	pub direction: Angle,
+	pub speed: Velocity,
+}
+
+/// The components of a wind relative to a course, such as a runway heading
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct WindComponents {
+	/// Component along the course - positive for a headwind, or negative for a tailwind
+	pub headwind: Velocity,
+	/// Component across the course - positive when blowing from the right, or negative when blowing from the left
+	pub crosswind: Velocity,
+}
+
+impl Wind {
+	/// Returns the wind which makes good `track` at `ground_speed` when flying `heading` at `true_airspeed`
+	pub fn from_triangle(heading: Angle, true_airspeed: Velocity, track: Angle, ground_speed: Velocity) -> Wind {
+		let east = ground_speed * track.sin() - true_airspeed * heading.sin();
+		let north = ground_speed * track.cos() - true_airspeed * heading.cos();
+
+		let speed = east.hypot(north);
+		if speed == Velocity::ZERO {
+			return Wind::default();
+		}
+
+		Wind {
+			direction: clamp_angle_cw(east.atan2(north) + Angle::HALF_TURN),
+			speed,
+		}
+	}
+
+	/// Returns the headwind and crosswind components relative to `course`
+	pub fn components(&self, course: Angle) -> WindComponents {
+		let relative = diff_angle(course, self.direction, Direction::Either);
+
+		WindComponents {
+			headwind: self.speed * relative.cos(),
+			crosswind: self.speed * relative.sin(),
+		}
+	}
+
+	/// Returns the angle from `heading` to the track made good when flying it at `true_airspeed` - positive when
+	/// drifting right, or negative when drifting left
+	pub fn drift_angle(&self, heading: Angle, true_airspeed: Velocity) -> Angle {
+		diff_angle(
+			heading,
+			self.track_for_heading(heading, true_airspeed).0,
+			Direction::Either,
+		)
+	}
+
+	/// Returns the east and north components of the motion of the air mass
+	pub fn vector(&self) -> (Velocity, Velocity) {
+		let towards = self.direction + Angle::HALF_TURN;
+
+		(self.speed * towards.sin(), self.speed * towards.cos())
+	}
+
+	/// Returns the true track and ground speed made good when flying `heading` at `true_airspeed`
+	pub fn track_for_heading(&self, heading: Angle, true_airspeed: Velocity) -> (Angle, Velocity) {
+		let (wind_east, wind_north) = self.vector();
+
+		let east = true_airspeed * heading.sin() + wind_east;
+		let north = true_airspeed * heading.cos() + wind_north;
+
+		(clamp_angle_cw(east.atan2(north)), east.hypot(north))
+	}
+
+	/// Returns the true heading to fly at `true_airspeed` to make good `track`, and the resulting ground speed.
+	///
+	/// Returns `None` if the wind is too strong to make good `track`
+	pub fn heading_for_track(&self, track: Angle, true_airspeed: Velocity) -> Option<(Angle, Velocity)> {
+		let towards = self.direction + Angle::HALF_TURN;
+
+		let crosswind: Ratio = self.speed * (towards - track).sin() / true_airspeed;
+		if crosswind.abs().value > 1.0 {
+			return None;
+		}
+
+		let correction = -crosswind.asin();
+		let ground_speed = true_airspeed * correction.cos() + self.speed * (towards - track).cos();
+		if ground_speed <= Velocity::ZERO {
+			return None;
+		}
+
+		Some((clamp_angle_cw(track + correction), ground_speed))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use uom::si::{angle::degree, velocity::knot};
+
+	use super::*;
+	use crate::assert_uom_eq;
+
+	#[test]
+	fn test_track_for_heading() {
+		let wind = Wind {
+			direction: Angle::new::<degree>(270.0),
+			speed: Velocity::new::<knot>(30.0),
+		};
+
+		let (track, ground_speed) = wind.track_for_heading(Angle::new::<degree>(90.0), Velocity::new::<knot>(200.0));
+		assert_uom_eq!(track, Angle::new::<degree>(90.0));
+		assert_uom_eq!(ground_speed, Velocity::new::<knot>(230.0));
+
+		let (track, ground_speed) = wind.track_for_heading(Angle::ZERO, Velocity::new::<knot>(40.0));
+		assert_uom_eq!(track, Angle::new::<degree>(90.0 - 53.13010235415598));
+		assert_uom_eq!(ground_speed, Velocity::new::<knot>(50.0));
+	}
+
+	#[test]
+	fn test_vector() {
+		let wind = Wind {
+			direction: Angle::new::<degree>(270.0),
+			speed: Velocity::new::<knot>(30.0),
+		};
+		let (east, north) = wind.vector();
+		assert_uom_eq!(east, Velocity::new::<knot>(30.0), 1e-9);
+		assert_uom_eq!(north, Velocity::ZERO, 1e-9);
+
+		let wind = Wind {
+			direction: Angle::new::<degree>(135.0),
+			speed: Velocity::new::<knot>(20.0),
+		};
+		let (east, north) = wind.vector();
+		assert_uom_eq!(east, Velocity::new::<knot>(-20.0 * 0.5f64.sqrt()), 1e-9);
+		assert_uom_eq!(north, Velocity::new::<knot>(20.0 * 0.5f64.sqrt()), 1e-9);
+	}
+
+	#[test]
+	fn test_heading_for_track() {
+		let wind = Wind {
+			direction: Angle::new::<degree>(270.0),
+			speed: Velocity::new::<knot>(30.0),
+		};
+
+		let (heading, ground_speed) = wind
+			.heading_for_track(Angle::ZERO, Velocity::new::<knot>(50.0))
+			.unwrap();
+		assert_uom_eq!(heading, Angle::new::<degree>(360.0 - 36.86989764584402));
+		assert_uom_eq!(ground_speed, Velocity::new::<knot>(40.0));
+
+		assert!(wind
+			.heading_for_track(Angle::new::<degree>(270.0), Velocity::new::<knot>(25.0))
+			.is_none());
+	}
+
+	#[test]
+	fn test_from_triangle() {
+		let wind = Wind {
+			direction: Angle::new::<degree>(320.0),
+			speed: Velocity::new::<knot>(45.0),
+		};
+		let heading = Angle::new::<degree>(75.0);
+		let true_airspeed = Velocity::new::<knot>(180.0);
+		let (track, ground_speed) = wind.track_for_heading(heading, true_airspeed);
+
+		let solved = Wind::from_triangle(heading, true_airspeed, track, ground_speed);
+		assert_uom_eq!(solved.direction, wind.direction, 1e-9);
+		assert_uom_eq!(solved.speed, wind.speed, 1e-9);
+
+		assert_eq!(
+			Wind::from_triangle(heading, true_airspeed, heading, true_airspeed),
+			Wind::default()
+		);
+	}
+
+	#[test]
+	fn test_components_and_drift() {
+		let wind = Wind {
+			direction: Angle::new::<degree>(300.0),
+			speed: Velocity::new::<knot>(20.0),
+		};
+
+		let components = wind.components(Angle::new::<degree>(240.0));
+		assert_uom_eq!(components.headwind, Velocity::new::<knot>(10.0), 1e-9);
+		assert_uom_eq!(components.crosswind, Velocity::new::<knot>(20.0 * 0.75f64.sqrt()), 1e-9);
+
+		let components = wind.components(Angle::new::<degree>(30.0));
+		assert_uom_eq!(components.headwind, Velocity::ZERO, 1e-9);
+		assert_uom_eq!(components.crosswind, Velocity::new::<knot>(-20.0), 1e-9);
+
+		// Blown left by a wind from the right
+		let drift = wind.drift_angle(Angle::new::<degree>(210.0), Velocity::new::<knot>(100.0));
+		assert_uom_eq!(drift, -Angle::new::<degree>(0.2f64.atan().to_degrees()), 1e-9);
+	}
+}